@@ -0,0 +1,57 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use reg_map::RegMap;
+
+#[repr(C)]
+#[derive(RegMap)]
+struct Bench {
+    data: [u32; 1024],
+}
+
+fn iter(c: &mut Criterion) {
+    let mut regs = Bench { data: [1; 1024] };
+    let ptr = BenchPtr::from_mut(&mut regs);
+
+    let mut group = c.benchmark_group("RegArray");
+    group.bench_function("idx", |b| {
+        b.iter(|| {
+            let data = black_box(ptr.data());
+            (0..data.len()).map(|i| data.idx(i).read()).sum::<u32>()
+        })
+    });
+    group.bench_function("iter", |b| {
+        b.iter(|| black_box(ptr.data()).iter().map(|r| r.read()).sum::<u32>())
+    });
+    group.bench_function("iter_unchecked", |b| {
+        b.iter(|| {
+            black_box(ptr.data())
+                .iter_unchecked()
+                .map(|r| r.read())
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("iter_len", |b| {
+        b.iter(|| {
+            let mut it = black_box(ptr.data()).iter();
+            let mut sum = 0;
+            while it.len() > 0 {
+                sum += it.next().unwrap().read();
+            }
+            sum
+        })
+    });
+    group.bench_function("iter_reset", |b| {
+        let mut it = ptr.data().iter();
+        b.iter(|| {
+            it.reset();
+            black_box(&mut it).map(|r| r.read()).sum::<u32>()
+        })
+    });
+    group.bench_function("iter_repeated", |b| {
+        let data = ptr.data();
+        b.iter(|| black_box(&data).iter().map(|r| r.read()).sum::<u32>())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, iter);
+criterion_main!(benches);
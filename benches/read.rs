@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use reg_map::RegMap;
+
+#[repr(C)]
+#[derive(RegMap)]
+struct Bench {
+    r32: u32,
+    r64: u64,
+    r128: u128,
+}
+
+fn read(c: &mut Criterion) {
+    let mut regs = Bench {
+        r32: 1,
+        r64: 2,
+        r128: 3,
+    };
+    let ptr = BenchPtr::from_mut(&mut regs);
+
+    let mut group = c.benchmark_group("Reg::read");
+    group.bench_function("u32", |b| b.iter(|| black_box(ptr.r32()).read()));
+    group.bench_function("u64", |b| b.iter(|| black_box(ptr.r64()).read()));
+    group.bench_function("u128", |b| b.iter(|| black_box(ptr.r128()).read()));
+    group.finish();
+}
+
+criterion_group!(benches, read);
+criterion_main!(benches);
@@ -1,6 +1,7 @@
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::{Data, DataStruct, DeriveInput, Fields, Ident, Result, Type, TypeArray, Visibility};
 
 macro_rules! bail {
@@ -15,7 +16,7 @@ macro_rules! bail {
     };
 }
 
-#[proc_macro_derive(RegMap, attributes(reg))]
+#[proc_macro_derive(RegMap, attributes(reg, reg_map))]
 pub fn reg_map_derive(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input);
 
@@ -31,6 +32,17 @@ fn impl_reg(ast: &DeriveInput) -> Result<TokenStream> {
     // check if using a compatible repr
     check_repr(ast)?;
 
+    let MapAttr {
+        krate,
+        ptr_derive,
+        fields: with_fields,
+        at_path,
+        tail,
+        secure_clear,
+        zeroed: with_zeroed,
+        flatten,
+    } = parse_map_attr(ast)?;
+
     if let Data::Struct(DataStruct {
         struct_token: _,
         ref fields,
@@ -40,14 +52,46 @@ fn impl_reg(ast: &DeriveInput) -> Result<TokenStream> {
         let ptr_name = Ident::new(&format!("{}Ptr", name), Span::call_site());
         let mod_name = Ident::new(&format!("_mod_{}", name), Span::call_site());
         let mut all_methods = quote!();
+        let mut flat_methods = quote!();
+        let mut field_infos = quote!();
+        let mut field_count = 0usize;
+        let mut offsets = quote!();
+        let mut zeroed = quote!();
+        let mut tail_check = quote!();
         if let Fields::Named(named) = fields {
             for field in named.named.iter() {
-                all_methods.extend(parse_field(field)?);
+                offsets.extend(parse_offset(name, field));
+                all_methods.extend(parse_field(field, &krate)?);
+                flat_methods.extend(parse_flat_field(field, &krate)?);
+                if let Some(info) = parse_field_info(field, &krate)? {
+                    field_infos.extend(info);
+                    field_count += 1;
+                }
+            }
+            if at_path {
+                all_methods.extend(parse_at_path(name, &named.named, &krate)?);
+            }
+            if tail {
+                let (method, check) = parse_tail(name, &named.named, &krate)?;
+                all_methods.extend(method);
+                tail_check = check;
+            }
+            if secure_clear {
+                all_methods.extend(parse_secure_clear(&ptr_name, &named.named, &krate)?);
+            }
+            if with_zeroed {
+                zeroed = parse_zeroed(name, &named.named)?;
             }
         } else {
             bail!(ast, "RegMap derive supports only structs with named fields");
         }
         let doc_msg_top = format!("A pointer to the register map `{name}`.");
+        let mut doc_top = quote!(#[doc = #doc_msg_top]);
+        let struct_docs = parse_docs(&ast.attrs);
+        if !struct_docs.is_empty() {
+            doc_top.extend(quote!(#[doc = ""]));
+            doc_top.extend(struct_docs);
+        }
         let doc_msg_from_nonnull = format!(
             "\
             Creates a new `{ptr_name}`, a pointer to `{name}`.\n\
@@ -65,29 +109,59 @@ fn impl_reg(ast: &DeriveInput) -> Result<TokenStream> {
             - `ptr` must not be null;\n\
             - `ptr` must point to a valid instance of `{name}`;\n\
             - `ptr` must be valid for the whole lifetime `'a`;\n\
+            - all fields of `{name}` must allow volatile reads/writes.\n\
+            \n\
+            Being a `const fn`, this function does not check the alignment of `ptr`. In debug \
+            builds, accessing the registers through a misaligned pointer panics."
+        );
+        let doc_msg_from_static = format!(
+            "\
+            Creates a new `{ptr_name}<'static>`, a pointer to `{name}` valid for the whole \
+            program, e.g. to a `static mut` or to a peripheral at a fixed address.\n\
+            \n\
+            Same as `from_ptr`, with the lifetime fixed to `'static`.\n\
+            \n\
+            # Safety\n\
+            - `ptr` must not be null;\n\
+            - `ptr` must point to a valid instance of `{name}`;\n\
+            - `ptr` must be valid for the rest of the program;\n\
             - all fields of `{name}` must allow volatile reads/writes."
         );
         let doc_msg_from_mut =
             format!("Return a pointer to `{name}` from a mutable (exclusive) reference.");
-        let all = quote!(
+        let doc_msg_from_unsafe_cell = format!(
+            "\
+            Return a pointer to `{name}` from a shared reference to an `UnsafeCell`.\n\
+            \n\
+            No references to the contents of the cell, e.g. created from `UnsafeCell::get`, can \
+            be live while the returned pointer is used."
+        );
+        let fields_method = if with_fields {
+            quote!(
+                /// Returns an iterator over the runtime information of all the fields of integer,
+                /// non-zero integer, boolean or floating-point type, in declaration order.
+                #[inline]
+                pub fn fields(&self) -> impl ::core::iter::Iterator<Item = #krate::FieldInfo> {
+                    let fields: [::core::option::Option<#krate::FieldInfo>; #field_count] =
+                        [#field_infos];
+                    fields.into_iter().flatten()
+                }
+            )
+        } else {
+            quote!()
+        };
+        let mut all = quote!(
             #[allow(non_snake_case)]
             mod #mod_name {
                 use super::*;
-                #[doc = #doc_msg_top]
-                #ptr_vis struct #ptr_name<'a> {
+                #doc_top
+                #ptr_derive
+                #ptr_vis struct #ptr_name<'a, A: #krate::access::Access = #krate::access::ReadWrite> {
                     ptr: ::core::ptr::NonNull<#name>,
                     _ref: ::core::marker::PhantomData<&'a #name>,
+                    _acs: ::core::marker::PhantomData<A>,
                 }
                 impl<'a> #ptr_name<'a> {
-                    #[doc = #doc_msg_from_nonnull]
-                    #[inline]
-                    const unsafe fn from_nonnull(ptr: ::core::ptr::NonNull<#name>) -> Self {
-                        Self {
-                            ptr,
-                            _ref: ::core::marker::PhantomData,
-                        }
-                    }
-
                     #[doc = #doc_msg_from_ptr]
                     #[inline]
                     pub const unsafe fn from_ptr(ptr: *mut #name) -> Self {
@@ -102,35 +176,72 @@ fn impl_reg(ast: &DeriveInput) -> Result<TokenStream> {
                         unsafe { Self::from_ptr(reg) }
                     }
 
+                    #[doc = #doc_msg_from_unsafe_cell]
+                    #[inline]
+                    pub const fn from_unsafe_cell(cell: &'a ::core::cell::UnsafeCell<#name>) -> Self {
+                        // safe because `UnsafeCell` allows mutation through a shared reference,
+                        // and the borrow is valid for 'a
+                        unsafe { Self::from_ptr(cell.get()) }
+                    }
+                    #offsets
+                }
+                impl #ptr_name<'static> {
+                    #[doc = #doc_msg_from_static]
+                    #[inline]
+                    pub const unsafe fn from_static(ptr: *mut #name) -> Self {
+                        Self::from_ptr(ptr)
+                    }
+                }
+                impl<'a, A: #krate::access::Access> #ptr_name<'a, A> {
+                    #[doc = #doc_msg_from_nonnull]
+                    #[inline]
+                    const unsafe fn from_nonnull(ptr: ::core::ptr::NonNull<#name>) -> Self {
+                        Self {
+                            ptr,
+                            _ref: ::core::marker::PhantomData,
+                            _acs: ::core::marker::PhantomData,
+                        }
+                    }
+
                     /// Returns a raw pointer to the underlying register map.
                     #[inline]
                     pub const fn as_ptr(&self) -> *mut #name {
                         self.ptr.as_ptr()
                     }
                     #all_methods
+                    #fields_method
                 }
-                unsafe impl<'a> ::reg_map::RegMapPtr<'a> for #ptr_name<'a> {
+                unsafe impl<'a, A: #krate::access::Access> #krate::RegMapPtr<'a> for #ptr_name<'a, A> {
                     type RegMap = #name;
                     #[inline]
                     unsafe fn from_nonnull(ptr: ::core::ptr::NonNull<Self::RegMap>) -> Self {
                         Self::from_nonnull(ptr)
                     }
                     #[inline]
+                    #[track_caller]
                     unsafe fn from_ptr(ptr: *mut Self::RegMap) -> Self {
-                        Self::from_ptr(ptr)
+                        ::core::debug_assert!(ptr.is_aligned(), "misaligned register map pointer");
+                        Self::from_nonnull(::core::ptr::NonNull::new_unchecked(ptr))
                     }
                     #[inline]
                     fn from_mut(reg: &'a mut Self::RegMap) -> Self {
-                        Self::from_mut(reg)
+                        // safe because we are the only borrowers (&mut)
+                        // and the borrow is valid for 'a
+                        unsafe { Self::from_nonnull(::core::ptr::NonNull::from(reg)) }
                     }
                     #[inline]
                     fn as_ptr(&self) -> *mut Self::RegMap {
                         self.as_ptr()
                     }
                 }
+                #tail_check
             }
             #vis use #mod_name::#ptr_name;
         );
+        all.extend(zeroed);
+        if flatten {
+            all.extend(flatten_macro(name, flat_methods));
+        }
         Ok(all.into())
     } else {
         bail!(ast, "RegMap derive supports only structs")
@@ -176,10 +287,79 @@ fn is_integer(ident: &Ident) -> bool {
         || ident == "i128"
 }
 
+fn is_nonzero(type_path: &syn::TypePath) -> bool {
+    let Some(last) = type_path.path.segments.last() else {
+        return false;
+    };
+    last.arguments.is_none()
+        && (last.ident == "NonZeroU8"
+            || last.ident == "NonZeroU16"
+            || last.ident == "NonZeroU32"
+            || last.ident == "NonZeroU64"
+            || last.ident == "NonZeroU128"
+            || last.ident == "NonZeroI8"
+            || last.ident == "NonZeroI16"
+            || last.ident == "NonZeroI32"
+            || last.ident == "NonZeroI64"
+            || last.ident == "NonZeroI128")
+}
+
+/// The integer type underlying the non-zero integer type `type_path`, e.g. `u32` for
+/// `NonZeroU32`.
+fn nonzero_integer(type_path: &syn::TypePath) -> Ident {
+    let last = &type_path
+        .path
+        .segments
+        .last()
+        .expect("checked by is_nonzero")
+        .ident;
+    let name = last.to_string();
+    let int = name.strip_prefix("NonZero").expect("checked by is_nonzero");
+    Ident::new(&int.to_lowercase(), last.span())
+}
+
+fn is_bool(type_path: &syn::TypePath) -> bool {
+    type_path.qself.is_none() && type_path.path.is_ident("bool")
+}
+
+fn is_float(type_path: &syn::TypePath) -> bool {
+    type_path.qself.is_none() && (type_path.path.is_ident("f32") || type_path.path.is_ident("f64"))
+}
+
+/// `true` if `type_path` is stored as an integer but read as a different type, i.e. a non-zero
+/// integer type, `bool`, `f32` or `f64`.
+fn is_cast(type_path: &syn::TypePath) -> bool {
+    is_nonzero(type_path) || is_bool(type_path) || is_float(type_path)
+}
+
+/// `true` if `ty` is a non-zero integer type, or an array of them (of any dimension).
+fn contains_nonzero(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => is_nonzero(type_path),
+        Type::Array(TypeArray { elem, .. }) => contains_nonzero(elem),
+        _ => false,
+    }
+}
+
+/// `true` if `ty` is a non-zero integer type, `bool` or a float, or an array of them (of any
+/// dimension).
+fn contains_cast(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => is_cast(type_path),
+        Type::Array(TypeArray { elem, .. }) => contains_cast(elem),
+        _ => false,
+    }
+}
+
 mod kw {
     syn::custom_keyword!(RO);
     syn::custom_keyword!(WO);
     syn::custom_keyword!(RW);
+    syn::custom_keyword!(RC);
+    syn::custom_keyword!(W1C);
+    syn::custom_keyword!(via);
+    syn::custom_keyword!(flatten);
+    syn::custom_keyword!(bytes);
 }
 #[derive(Default)]
 enum RegAccess {
@@ -187,6 +367,8 @@ enum RegAccess {
     WO,
     #[default]
     RW,
+    RC,
+    W1C,
 }
 impl syn::parse::Parse for RegAccess {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -197,24 +379,209 @@ impl syn::parse::Parse for RegAccess {
             input.parse::<kw::WO>().map(|_| RegAccess::WO)
         } else if lookahead.peek(kw::RW) {
             input.parse::<kw::RW>().map(|_| RegAccess::RW)
+        } else if lookahead.peek(kw::RC) {
+            input.parse::<kw::RC>().map(|_| RegAccess::RC)
+        } else if lookahead.peek(kw::W1C) {
+            input.parse::<kw::W1C>().map(|_| RegAccess::W1C)
         } else {
             Err(lookahead.error())
         }
     }
 }
-impl quote::ToTokens for RegAccess {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+/// Arguments of the `#[reg(...)]` field attribute, e.g. `#[reg(RO, via = MyType)]`,
+/// `#[reg(flatten)]` or `#[reg(bytes)]`.
+#[derive(Default)]
+struct RegAttr {
+    access: RegAccess,
+    via: Option<Type>,
+    flatten: bool,
+    bytes: bool,
+}
+impl syn::parse::Parse for RegAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut access = None;
+        let mut via = None;
+        let mut flatten = false;
+        let mut bytes = false;
+        while !input.is_empty() {
+            if input.peek(kw::bytes) {
+                let kw = input.parse::<kw::bytes>()?;
+                if bytes {
+                    return Err(syn::Error::new_spanned(kw, "duplicate `bytes` argument"));
+                }
+                bytes = true;
+            } else if input.peek(kw::flatten) {
+                let kw = input.parse::<kw::flatten>()?;
+                if flatten {
+                    return Err(syn::Error::new_spanned(kw, "duplicate `flatten` argument"));
+                }
+                flatten = true;
+            } else if input.peek(kw::via) {
+                let kw = input.parse::<kw::via>()?;
+                if via.is_some() {
+                    return Err(syn::Error::new_spanned(kw, "duplicate `via` argument"));
+                }
+                input.parse::<syn::Token![=]>()?;
+                via = Some(input.parse()?);
+            } else {
+                let span = input.span();
+                if access.is_some() {
+                    return Err(syn::Error::new(span, "duplicate access argument"));
+                }
+                access = Some(input.parse()?);
+            }
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(Self {
+            access: access.unwrap_or_default(),
+            via,
+            flatten,
+            bytes,
+        })
+    }
+}
+fn parse_reg_attr(field: &syn::Field) -> Result<RegAttr> {
+    let mut reg_attr = RegAttr::default();
+    for attr in &field.attrs {
+        if attr.path().is_ident("reg") {
+            reg_attr = attr.parse_args()?;
+        }
+    }
+    Ok(reg_attr)
+}
+
+impl RegAccess {
+    fn to_tokens(&self, krate: &syn::Path) -> proc_macro2::TokenStream {
         match self {
-            RegAccess::RO => tokens.extend(quote!(::reg_map::access::ReadOnly)),
-            RegAccess::WO => tokens.extend(quote!(::reg_map::access::WriteOnly)),
-            RegAccess::RW => tokens.extend(quote!(::reg_map::access::ReadWrite)),
+            RegAccess::RO => quote!(#krate::access::ReadOnly),
+            RegAccess::WO => quote!(#krate::access::WriteOnly),
+            RegAccess::RW => quote!(#krate::access::ReadWrite),
+            RegAccess::RC => quote!(#krate::access::ReadClear),
+            RegAccess::W1C => quote!(#krate::access::Write1Clear),
+        }
+    }
+    /// The access of a field in a map pointer with access `acs`, i.e. `acs` restricted by `self`.
+    fn restrict(
+        &self,
+        krate: &syn::Path,
+        acs: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        match self {
+            RegAccess::RW => quote!(#acs),
+            _ => {
+                let access = self.to_tokens(krate);
+                quote!(<#acs as #krate::access::Restrict<#access>>::Output)
+            }
+        }
+    }
+    /// The `where` predicate required by `restrict`, if any.
+    fn bound(
+        &self,
+        krate: &syn::Path,
+        acs: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        match self {
+            RegAccess::RW => quote!(),
+            _ => {
+                let access = self.to_tokens(krate);
+                quote!(#acs: #krate::access::Restrict<#access>,)
+            }
         }
     }
 }
 
+/// Arguments of the `#[reg_map(...)]` struct attribute, e.g. `#[reg_map(crate = "path")]`.
+struct MapAttr {
+    krate: syn::Path,
+    /// `#[derive(...)]` attribute for the pointer type, empty if none
+    ptr_derive: proc_macro2::TokenStream,
+    /// whether to generate the `fields` method
+    fields: bool,
+    /// whether to generate the `at_path` method
+    at_path: bool,
+    /// whether to generate the `tail` method
+    tail: bool,
+    /// whether to generate the `secure_clear` method
+    secure_clear: bool,
+    /// whether to generate the `ZEROED` constant
+    zeroed: bool,
+    /// whether to generate the macro used to flatten this map into another, see `flatten_macro`
+    flatten: bool,
+}
+fn parse_map_attr(input: &DeriveInput) -> Result<MapAttr> {
+    let mut krate = None;
+    let mut derives = Vec::<syn::Path>::new();
+    let mut fields = false;
+    let mut at_path = false;
+    let mut tail = false;
+    let mut secure_clear = false;
+    let mut zeroed = false;
+    let mut flatten = false;
+    for attr in &input.attrs {
+        if attr.path().is_ident("reg_map") {
+            attr.parse_nested_meta(|meta| {
+                // #[reg_map(crate = "path")]
+                if meta.path.is_ident("crate") {
+                    if krate.is_some() {
+                        return Err(meta.error("duplicate `crate` argument"));
+                    }
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    krate = Some(lit.parse()?);
+                    return Ok(());
+                }
+
+                // #[reg_map(ptr_derive(Trait, ...))]
+                if meta.path.is_ident("ptr_derive") {
+                    return meta.parse_nested_meta(|inner| {
+                        derives.push(inner.path);
+                        Ok(())
+                    });
+                }
+
+                // #[reg_map(fields)], #[reg_map(at_path)], #[reg_map(tail)],
+                // #[reg_map(secure_clear)], #[reg_map(zeroed)], #[reg_map(flatten)]
+                for (flag, value) in [
+                    ("fields", &mut fields),
+                    ("at_path", &mut at_path),
+                    ("tail", &mut tail),
+                    ("secure_clear", &mut secure_clear),
+                    ("zeroed", &mut zeroed),
+                    ("flatten", &mut flatten),
+                ] {
+                    if meta.path.is_ident(flag) {
+                        if *value {
+                            return Err(meta.error(format!("duplicate `{flag}` argument")));
+                        }
+                        *value = true;
+                        return Ok(());
+                    }
+                }
+
+                Err(meta.error("RegMap derive found an unrecognized #[reg_map(...)] argument"))
+            })?;
+        }
+    }
+    let ptr_derive = if derives.is_empty() {
+        quote!()
+    } else {
+        quote!(#[derive(#(#derives),*)])
+    };
+    Ok(MapAttr {
+        krate: krate.unwrap_or_else(|| syn::parse_quote!(::reg_map)),
+        ptr_derive,
+        fields,
+        at_path,
+        tail,
+        secure_clear,
+        zeroed,
+        flatten,
+    })
+}
+
 fn check_repr(input: &DeriveInput) -> Result<()> {
     let mut repr_c = false;
-    let mut repr_align = None::<usize>;
 
     for attr in &input.attrs {
         if attr.path().is_ident("repr") {
@@ -231,13 +598,11 @@ fn check_repr(input: &DeriveInput) -> Result<()> {
                     return Err(meta.error("RegMap derive does not support #[repr(transparent)]"));
                 }
 
-                // #[repr(align(N))]
+                // #[repr(align(N))], validating N is left to the compiler
                 if meta.path.is_ident("align") {
                     let content;
                     syn::parenthesized!(content in meta.input);
-                    let lit: syn::LitInt = content.parse()?;
-                    let n: usize = lit.base10_parse()?;
-                    repr_align = Some(n);
+                    let _: proc_macro2::TokenStream = content.parse()?;
                     return Ok(());
                 }
 
@@ -258,17 +623,53 @@ fn check_repr(input: &DeriveInput) -> Result<()> {
     }
 }
 
-fn parse_field(field: &syn::Field) -> Result<proc_macro2::TokenStream> {
+fn parse_field(field: &syn::Field, krate: &syn::Path) -> Result<proc_macro2::TokenStream> {
     let name = field.ident.as_ref().expect("struct fields are named");
     let ty = &field.ty;
-    let ret_sig = parse_ret_type(field, ty)?;
-    let doc = parse_docs(field);
-    Ok(match ty {
+    let acs = quote!(A);
+    let ret_sig = parse_ret_type(field, ty, krate, &acs)?;
+    let RegAttr {
+        access,
+        flatten,
+        bytes,
+        ..
+    } = parse_reg_attr(field)?;
+    let access_bound = access.bound(krate, &acs);
+    let doc = parse_docs(&field.attrs);
+    // non-zero, boolean and float registers are accessed through a pointer to the underlying
+    // integer
+    let cast = if contains_cast(ty) {
+        quote!(.cast())
+    } else {
+        quote!()
+    };
+    let mut accessor = match ty {
+        Type::Array(TypeArray { .. }) if bytes => quote!(
+            #doc
+            #[inline]
+            pub fn #name (&self) -> #ret_sig where #access_bound {
+                unsafe { #krate::RegBytes::__MACRO_ONLY__from_ptr(::core::ptr::addr_of_mut!((*self.as_ptr()).#name)) }
+            }
+        ),
         Type::Array(TypeArray { .. }) => quote!(
             #doc
             #[inline]
-            pub fn #name (&self) -> #ret_sig {
-                unsafe { ::reg_map::RegArray::__MACRO_ONLY__from_ptr(::core::ptr::addr_of_mut!((*self.as_ptr()).#name)) }
+            pub fn #name (&self) -> #ret_sig where #access_bound {
+                unsafe { #krate::RegArray::__MACRO_ONLY__from_ptr(::core::ptr::addr_of_mut!((*self.as_ptr()).#name)#cast) }
+            }
+        ),
+        Type::Path(ref type_path) if is_nonzero(type_path) => quote!(
+            #doc
+            #[inline]
+            pub fn #name (&self) -> #ret_sig where #access_bound {
+                unsafe { #krate::RegNonZero::__MACRO_ONLY__from_ptr(::core::ptr::addr_of_mut!((*self.as_ptr()).#name)#cast) }
+            }
+        ),
+        Type::Path(ref type_path) if is_cast(type_path) => quote!(
+            #doc
+            #[inline]
+            pub fn #name (&self) -> #ret_sig where #access_bound {
+                unsafe { #krate::Reg::__MACRO_ONLY__from_ptr(::core::ptr::addr_of_mut!((*self.as_ptr()).#name)#cast) }
             }
         ),
         Type::Path(ref type_path) => {
@@ -277,17 +678,16 @@ fn parse_field(field: &syn::Field) -> Result<proc_macro2::TokenStream> {
                 quote!(
                     #doc
                     #[inline]
-                    pub fn #name (&self) -> #ret_sig {
-                        unsafe { ::reg_map::Reg::__MACRO_ONLY__from_ptr(::core::ptr::addr_of_mut!((*self.as_ptr()).#name)) }
+                    pub fn #name (&self) -> #ret_sig where #access_bound {
+                        unsafe { #krate::Reg::__MACRO_ONLY__from_ptr(::core::ptr::addr_of_mut!((*self.as_ptr()).#name)) }
                     }
                 )
             } else {
-                let ptr_ty = Ident::new(&format!("{}Ptr", ident), Span::call_site());
                 quote!(
                     #doc
                     #[inline]
-                    pub fn #name (&self) -> #ret_sig {
-                        unsafe { #ptr_ty::from_ptr(::core::ptr::addr_of_mut!((*self.as_ptr()).#name)) }
+                    pub fn #name (&self) -> #ret_sig where #access_bound {
+                        unsafe { #krate::RegMapPtr::from_ptr(::core::ptr::addr_of_mut!((*self.as_ptr()).#name)) }
                     }
                 )
             }
@@ -296,29 +696,405 @@ fn parse_field(field: &syn::Field) -> Result<proc_macro2::TokenStream> {
             field,
             "RegMap derive supports only field of type Path or Array"
         ),
-    })
+    };
+    if flatten {
+        let Type::Path(ref type_path) = ty else {
+            bail!(
+                field,
+                "RegMap derive supports `flatten` only on nested register maps"
+            );
+        };
+        let ident = &type_path.path.segments[0].ident;
+        if is_integer(ident) || is_cast(type_path) {
+            bail!(
+                field,
+                "RegMap derive supports `flatten` only on nested register maps"
+            );
+        }
+        // forward the accessors of the nested map, see `flatten_macro`
+        let flatten_macro = flatten_macro_name(ident);
+        let nested_acs = access.restrict(krate, &acs);
+        accessor.extend(quote!(
+            #flatten_macro!(#name, #nested_acs, [#access_bound]);
+        ));
+    }
+    Ok(accessor)
 }
 
-fn parse_ret_type(field: &syn::Field, ty: &Type) -> Result<proc_macro2::TokenStream> {
+/// Generates the `tail` method for maps whose last field is a zero-length array, modeling a C
+/// flexible array member, together with a compile-time check that the length of the array is
+/// zero. Fails for other maps.
+fn parse_tail(
+    map_name: &Ident,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+    krate: &syn::Path,
+) -> Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    const MSG: &str =
+        "RegMap derive supports `tail` only on maps whose last field is a zero-length array";
+    let Some(field) = fields.last() else {
+        bail!(map_name, MSG);
+    };
+    let Type::Array(TypeArray { elem, len, .. }) = &field.ty else {
+        bail!(map_name, MSG);
+    };
+    if parse_reg_attr(field)?.bytes {
+        bail!(map_name, MSG);
+    }
+    // a literal length is checked here, any other constant expression by the compiler
+    let check = match len {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => {
+            if int.base10_parse::<usize>()? != 0 {
+                bail!(len, MSG);
+            }
+            quote!()
+        }
+        _ => quote!(
+            const _: () = ::core::assert!(#len == 0, #MSG);
+        ),
+    };
+    let name = field.ident.as_ref().expect("struct fields are named");
+    let acs = quote!(A);
+    let elem_sig = parse_ret_type(field, elem, krate, &acs)?;
+    let access_bound = parse_reg_attr(field)?.access.bound(krate, &acs);
+    let doc_msg = format!(
+        "\
+        Access the flexible array member `{name}` as a `RegSlice` of `len` elements.\n\
+        \n\
+        # Safety\n\
+        The memory following the register map must hold `len` valid elements of the array \
+        `{name}`, for the whole lifetime `'a`."
+    );
+    let method = quote!(
+        #[doc = #doc_msg]
+        #[inline]
+        pub unsafe fn tail(&self, len: usize) -> #krate::RegSlice<'a, #elem_sig>
+        where
+            #access_bound
+        {
+            #krate::RegSlice::from_raw_parts(::core::ptr::addr_of_mut!((*self.as_ptr()).#name).cast(), len)
+        }
+    );
+    Ok((method, check))
+}
+
+/// Generates the `ZEROED` associated constant on maps whose fields are all integers, booleans,
+/// floats, or arrays of them. Fails for other maps.
+fn parse_zeroed(
+    map_name: &Ident,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+) -> Result<proc_macro2::TokenStream> {
+    /// The all-zero value of `ty`, if `ty` is an integer, a boolean, a float, or an array of them.
+    fn zero_expr(ty: &Type) -> Option<proc_macro2::TokenStream> {
+        match ty {
+            Type::Path(type_path) if is_integer(&type_path.path.segments[0].ident) => {
+                Some(quote!(0))
+            }
+            Type::Path(type_path) if is_bool(type_path) => Some(quote!(false)),
+            Type::Path(type_path) if is_float(type_path) => Some(quote!(0.0)),
+            Type::Array(TypeArray { elem, len, .. }) => {
+                let zero = zero_expr(elem)?;
+                Some(quote!([#zero; #len]))
+            }
+            _ => None,
+        }
+    }
+    let mut inits = quote!();
+    for field in fields {
+        let name = field.ident.as_ref().expect("struct fields are named");
+        let Some(zero) = zero_expr(&field.ty) else {
+            bail!(
+                field,
+                "RegMap derive supports `zeroed` only on maps whose fields are integers, booleans, floats, or arrays of them"
+            );
+        };
+        inits.extend(quote!(#name: #zero,));
+    }
+    let doc_msg = format!(
+        "\
+        A `{map_name}` with all registers set to zero, e.g. to initialize backing storage in a \
+        `static`."
+    );
+    Ok(quote!(
+        impl #map_name {
+            #[doc = #doc_msg]
+            pub const ZEROED: Self = Self { #inits };
+        }
+    ))
+}
+
+/// Generates the `secure_clear` method, writing zero to all the writable registers of the map and
+/// of its nested maps.
+fn parse_secure_clear(
+    ptr_name: &Ident,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+    krate: &syn::Path,
+) -> Result<proc_macro2::TokenStream> {
+    /// The statements clearing `expr`, a pointer to a value of type `ty`.
+    fn clear_stmts(
+        ty: &Type,
+        expr: proc_macro2::TokenStream,
+        bytes: bool,
+        krate: &syn::Path,
+    ) -> proc_macro2::TokenStream {
+        match ty {
+            Type::Array(TypeArray { len, .. }) if bytes => quote!(#expr.write([0; #len]);),
+            Type::Array(TypeArray { elem, .. }) => {
+                let inner = clear_stmts(elem, quote!(elem), bytes, krate);
+                quote!(for elem in #expr.iter() { #inner })
+            }
+            Type::Path(type_path)
+                if is_integer(&type_path.path.segments[0].ident) || is_cast(type_path) =>
+            {
+                quote!(
+                    #expr.write(#krate::integers::RegValue::from_bits(
+                        #krate::integers::Integer::ZERO,
+                    ));
+                )
+            }
+            _ => quote!(#expr.secure_clear();),
+        }
+    }
+    let mut stmts = quote!();
+    for field in fields {
+        let RegAttr { access, bytes, .. } = parse_reg_attr(field)?;
+        if matches!(access, RegAccess::RO | RegAccess::RC) || contains_nonzero(&field.ty) {
+            continue;
+        }
+        let name = field.ident.as_ref().expect("struct fields are named");
+        stmts.extend(clear_stmts(&field.ty, quote!(this.#name()), bytes, krate));
+    }
+    Ok(quote!(
+        /// Writes zero to all the writable registers, including the registers of nested maps, and
+        /// issues a compiler fence so that the writes are not elided or reordered with later
+        /// accesses.
+        ///
+        /// Read-only, read-to-clear and non-zero registers are not accessed. This is meant to
+        /// scrub sensitive data, e.g. key registers, after use.
+        #[inline]
+        pub fn secure_clear(&self)
+        where
+            A: #krate::access::Writable,
+        {
+            // SAFETY: same pointer as `self`, and `A` allows writing all the fields that are
+            // writable in a read-write map
+            let this = unsafe { #ptr_name::<'a, #krate::access::ReadWrite>::from_nonnull(self.ptr) };
+            #stmts
+            ::core::sync::atomic::compiler_fence(::core::sync::atomic::Ordering::SeqCst);
+        }
+    ))
+}
+
+/// Generates the associated constant with the byte offset of a field, e.g. `FIELD1_OFFSET`.
+fn parse_offset(map_name: &Ident, field: &syn::Field) -> proc_macro2::TokenStream {
+    let name = field.ident.as_ref().expect("struct fields are named");
+    let const_name = Ident::new(
+        &format!("{}_OFFSET", name.unraw().to_string().to_uppercase()),
+        name.span(),
+    );
+    let doc_msg = format!("The byte offset of the field `{name}` in `{map_name}`.");
+    quote!(
+        #[doc = #doc_msg]
+        pub const #const_name: usize = ::core::mem::offset_of!(#map_name, #name);
+    )
+}
+
+/// Generates the `at_path` method for maps made of a single (possibly multidimensional) array,
+/// descending the array by a slice of indices. Fails for other maps.
+fn parse_at_path(
+    map_name: &Ident,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+    krate: &syn::Path,
+) -> Result<proc_macro2::TokenStream> {
+    const MSG: &str = "RegMap derive supports `at_path` only on maps made of a single array field";
+    let mut iter = fields.iter();
+    let (Some(field), None) = (iter.next(), iter.next()) else {
+        bail!(map_name, MSG);
+    };
+    if !matches!(field.ty, Type::Array(_)) || parse_reg_attr(field)?.bytes {
+        bail!(map_name, MSG);
+    }
+    let name = field.ident.as_ref().expect("struct fields are named");
+    let acs = quote!(A);
+    let ret_sig = parse_ret_type(field, &field.ty, krate, &acs)?;
+    let access_bound = parse_reg_attr(field)?.access.bound(krate, &acs);
+    let doc_msg = format!(
+        "\
+        Access the innermost element of the array `{name}`, descending one dimension for each \
+        index in `indices`.\n\
+        \n\
+        Same as `self.{name}().at(indices)`, see `RegArray::at`.\n\
+        \n\
+        # Panics\n\
+        If the number of indices differs from the number of dimensions of the array, or if any \
+        index is out of bounds."
+    );
+    Ok(quote!(
+        #[doc = #doc_msg]
+        #[inline]
+        #[track_caller]
+        pub fn at_path(&self, indices: &[usize]) -> <#ret_sig as #krate::ArrayElem<'a>>::Innermost
+        where
+            #access_bound
+        {
+            self.#name().at(indices)
+        }
+    ))
+}
+
+/// Same as `parse_field`, but generating an accessor that forwards to a nested map, for use in
+/// the body of `flatten_macro`.
+fn parse_flat_field(field: &syn::Field, krate: &syn::Path) -> Result<proc_macro2::TokenStream> {
+    let name = field.ident.as_ref().expect("struct fields are named");
+    let acs = quote!($acs);
+    let ret_sig = parse_ret_type(field, &field.ty, krate, &acs)?;
+    let access_bound = parse_reg_attr(field)?.access.bound(krate, &acs);
+    let doc = parse_docs(&field.attrs);
+    Ok(quote!(
+        #doc
+        #[inline]
+        pub fn #name (&self) -> #ret_sig where $($bound)* #access_bound {
+            self.$group().#name()
+        }
+    ))
+}
+
+fn flatten_macro_name(ident: &Ident) -> Ident {
+    Ident::new(&format!("_reg_map_flatten_{}", ident), Span::call_site())
+}
+
+/// Generates a macro that, when invoked inside the `impl` block of a parent pointer, defines
+/// accessors for all the fields of this map that forward to the parent's accessor `$group`.
+///
+/// The parent passes the access of the nested pointer as `$acs`, and any `where` predicates
+/// required by `$group` as `$bound`. Only generated for maps with `#[reg_map(flatten)]`.
+fn flatten_macro(
+    name: &Ident,
+    flat_methods: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let macro_name = flatten_macro_name(name);
+    quote!(
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            ($group:ident, $acs:ty, [$($bound:tt)*]) => { #flat_methods };
+        }
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name;
+    )
+}
+
+fn parse_field_info(
+    field: &syn::Field,
+    krate: &syn::Path,
+) -> Result<Option<proc_macro2::TokenStream>> {
+    let name = field.ident.as_ref().expect("struct fields are named");
+    let ty = &field.ty;
+    let Type::Path(ref type_path) = ty else {
+        return Ok(None);
+    };
+    if !is_integer(&type_path.path.segments[0].ident) && !is_cast(type_path) {
+        return Ok(None);
+    }
+    let name_str = name.unraw().to_string();
+    let access = parse_reg_attr(field)?.access;
+    let access_kind = match access {
+        RegAccess::RO => quote!(#krate::access::AccessKind::ReadOnly),
+        RegAccess::WO => quote!(#krate::access::AccessKind::WriteOnly),
+        RegAccess::RW => quote!(#krate::access::AccessKind::ReadWrite),
+        RegAccess::RC => quote!(#krate::access::AccessKind::ReadClear),
+        RegAccess::W1C => quote!(#krate::access::AccessKind::Write1Clear),
+    };
+    // `None` if the field is not accessible, i.e. read-only in a write-only map or vice versa
+    Ok(Some(quote!(
+        match <A as #krate::access::Access>::KIND.restrict(#access_kind) {
+            ::core::option::Option::Some(access) => ::core::option::Option::Some(#krate::FieldInfo {
+                name: #name_str,
+                addr: unsafe { ::core::ptr::addr_of_mut!((*self.as_ptr()).#name) } as usize,
+                width: 8 * ::core::mem::size_of::<#ty>(),
+                access,
+            }),
+            ::core::option::Option::None => ::core::option::Option::None,
+        },
+    )))
+}
+
+fn parse_ret_type(
+    field: &syn::Field,
+    ty: &Type,
+    krate: &syn::Path,
+    acs: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream> {
     match ty {
+        Type::Array(TypeArray { elem, len, .. }) if parse_reg_attr(field)?.bytes => {
+            let RegAttr { access, via, .. } = parse_reg_attr(field)?;
+            if via.is_some() {
+                bail!(field, "RegMap derive does not support `via` with `bytes`");
+            }
+            match elem.as_ref() {
+                Type::Path(type_path) if type_path.path.is_ident("u8") => {}
+                _ => bail!(
+                    field,
+                    "RegMap derive supports `bytes` only on fields of type `[u8; N]`"
+                ),
+            }
+            let access = access.restrict(krate, acs);
+            Ok(quote!(#krate::RegBytes<'a, {#len}, #access>))
+        }
         Type::Array(TypeArray { elem, len, .. }) => {
             // recursive!
-            let inner_sig = parse_ret_type(field, elem)?;
-            Ok(quote!(::reg_map::RegArray<'a, #inner_sig, {#len}>))
+            let inner_sig = parse_ret_type(field, elem, krate, acs)?;
+            Ok(quote!(#krate::RegArray<'a, #inner_sig, {#len}>))
         }
         Type::Path(ref type_path) => {
             let ident = &type_path.path.segments[0].ident;
-            if is_integer(ident) {
-                let mut access = RegAccess::default();
-                for attr in &field.attrs {
-                    if attr.path().is_ident("reg") {
-                        access = attr.parse_args()?;
-                    }
+            if parse_reg_attr(field)?.bytes {
+                bail!(
+                    field,
+                    "RegMap derive supports `bytes` only on fields of type `[u8; N]`"
+                );
+            }
+            if is_nonzero(type_path) {
+                let RegAttr { access, via, .. } = parse_reg_attr(field)?;
+                if via.is_some() {
+                    bail!(
+                        field,
+                        "RegMap derive supports `via` only on fields of integer type"
+                    );
+                }
+                let access = access.restrict(krate, acs);
+                let int = nonzero_integer(type_path);
+                Ok(quote!(#krate::RegNonZero<'a, #int, #access>))
+            } else if is_bool(type_path) || is_float(type_path) {
+                let RegAttr { access, via, .. } = parse_reg_attr(field)?;
+                if via.is_some() {
+                    bail!(
+                        field,
+                        "RegMap derive supports `via` only on fields of integer type"
+                    );
+                }
+                let access = access.restrict(krate, acs);
+                Ok(quote!(#krate::Reg<'a, #type_path, #access>))
+            } else if is_integer(ident) {
+                let RegAttr { access, via, .. } = parse_reg_attr(field)?;
+                let access = access.restrict(krate, acs);
+                match via {
+                    Some(via) => Ok(quote!(#krate::Reg<'a, #via, #access>)),
+                    None => Ok(quote!(#krate::Reg<'a, #ident, #access>)),
                 }
-                Ok(quote!(::reg_map::Reg<'a, #ident, #access>))
             } else {
+                let RegAttr { access, via, .. } = parse_reg_attr(field)?;
+                if via.is_some() {
+                    bail!(
+                        field,
+                        "RegMap derive supports `via` only on fields of integer type"
+                    );
+                }
+                let access = access.restrict(krate, acs);
                 let ptr_ty = Ident::new(&format!("{}Ptr", ident), Span::call_site());
-                Ok(quote!(#ptr_ty<'a>))
+                Ok(quote!(#ptr_ty<'a, #access>))
             }
         }
         _ => bail!(
@@ -327,9 +1103,9 @@ fn parse_ret_type(field: &syn::Field, ty: &Type) -> Result<proc_macro2::TokenStr
         ),
     }
 }
-fn parse_docs(field: &syn::Field) -> proc_macro2::TokenStream {
+fn parse_docs(attrs: &[syn::Attribute]) -> proc_macro2::TokenStream {
     let mut docs = quote!();
-    for attr in &field.attrs {
+    for attr in attrs {
         if attr.path().is_ident("doc") {
             let text = &attr
                 .meta
@@ -21,47 +21,258 @@ pub struct WriteOnly {}
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ReadWrite {}
 
+/// A zero-sized type indicating that a register provides only read access, and that reading it
+/// has the side effect of clearing it, e.g. a status register with read-to-clear flags.
+///
+/// Reads are performed exactly as for [`ReadOnly`] registers: the distinction is only at the type
+/// level, to document the side effect. Implements the [`Readable`] trait.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReadClear {}
+
+/// A zero-sized type indicating that a register provides read access, and write access with
+/// write-1-to-clear semantics, e.g. an interrupt-flag register.
+///
+/// Writing a `1` to a bit clears the corresponding flag, and writing a `0` leaves it unchanged.
+/// Flags are cleared with a single write of the mask of the flags to clear, see
+/// [`Reg::clear_flags`](crate::Reg::clear_flags). Read-modify-write operations, such as
+/// [`Reg::modify`](crate::Reg::modify), are not available: writing back the value read would
+/// clear all the flags that are set.
+///
+/// Implements the [`Readable`] and [`Writable`] traits, but not [`Modifiable`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Write1Clear {}
+
 /// Marker trait required by traits [`Readable`] and [`Writable`];
 ///
 /// ⚠️ This trait is sealed and cannot be implemented for types outside of this crate.
 pub trait Access:
     Debug + Default + Copy + Eq + Ord + Hash + Sized + Send + Sync + 'static + private::Sealed
 {
+    /// The access permission as a runtime value.
+    const KIND: AccessKind;
+
+    /// `true` for [`ReadOnly`], [`ReadClear`], [`ReadWrite`] and [`Write1Clear`], i.e. if the
+    /// type implements [`Readable`].
+    const READABLE: bool;
+
+    /// `true` for [`WriteOnly`], [`ReadWrite`] and [`Write1Clear`], i.e. if the type implements
+    /// [`Writable`].
+    const WRITABLE: bool;
+}
+
+/// Access permission of a register as a runtime value, see [`Access::KIND`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AccessKind {
+    /// See [`ReadOnly`].
+    ReadOnly,
+    /// See [`WriteOnly`].
+    WriteOnly,
+    /// See [`ReadWrite`].
+    ReadWrite,
+    /// See [`ReadClear`].
+    ReadClear,
+    /// See [`Write1Clear`].
+    Write1Clear,
+}
+impl AccessKind {
+    /// Returns `true` for [`AccessKind::ReadOnly`], [`AccessKind::ReadClear`],
+    /// [`AccessKind::ReadWrite`] and [`AccessKind::Write1Clear`].
+    #[inline]
+    pub const fn is_readable(self) -> bool {
+        matches!(
+            self,
+            AccessKind::ReadOnly
+                | AccessKind::ReadClear
+                | AccessKind::ReadWrite
+                | AccessKind::Write1Clear
+        )
+    }
+    /// Returns `true` for [`AccessKind::WriteOnly`], [`AccessKind::ReadWrite`] and
+    /// [`AccessKind::Write1Clear`].
+    #[inline]
+    pub const fn is_writable(self) -> bool {
+        matches!(
+            self,
+            AccessKind::WriteOnly | AccessKind::ReadWrite | AccessKind::Write1Clear
+        )
+    }
+    /// Runtime counterpart of [`Restrict`]: returns the access permission of a field with access
+    /// `field` in a register map with access `self`.
+    ///
+    /// Returns `None` when combining read-only and write-only permissions.
+    #[inline]
+    pub const fn restrict(self, field: AccessKind) -> Option<AccessKind> {
+        match (self, field) {
+            (AccessKind::ReadWrite, field) => Some(field),
+            (map, AccessKind::ReadWrite) => Some(map),
+            (AccessKind::Write1Clear, field) => Some(field),
+            (map, AccessKind::Write1Clear) => Some(map),
+            (AccessKind::ReadOnly, AccessKind::ReadOnly) => Some(AccessKind::ReadOnly),
+            (AccessKind::ReadOnly | AccessKind::ReadClear, AccessKind::ReadClear)
+            | (AccessKind::ReadClear, AccessKind::ReadOnly) => Some(AccessKind::ReadClear),
+            (AccessKind::WriteOnly, AccessKind::WriteOnly) => Some(AccessKind::WriteOnly),
+            _ => None,
+        }
+    }
 }
 
-/// Marker trait for readable registers implemented by types [`ReadOnly`] and [`ReadWrite`].
+/// Marker trait for readable registers implemented by types [`ReadOnly`], [`ReadClear`],
+/// [`ReadWrite`] and [`Write1Clear`].
 ///
 /// ⚠️ This trait is sealed and cannot be implemented for types outside of this crate.
 #[diagnostic::on_unimplemented(
     message = "cannot read from a write-only register",
     label = "method cannot be called on write-only registers",
     note = "the register is write only because it was annotated with the attribute
-  `#[reg(WO)]` in the register-map definition"
+  `#[reg(WO)]` in the register-map definition, or is part of a nested
+  register map annotated with it"
 )]
 pub trait Readable: Access {}
 
-/// Marker trait for writable registers implemented by types [`WriteOnly`] and [`ReadWrite`].
+/// Marker trait for writable registers implemented by types [`WriteOnly`], [`ReadWrite`] and
+/// [`Write1Clear`].
 ///
 /// ⚠️ This trait is sealed and cannot be implemented for types outside of this crate.
 #[diagnostic::on_unimplemented(
     message = "cannot write to a read-only register",
     label = "method cannot be called on read-only registers",
     note = "the register is read only because it was annotated with the attribute
-  `#[reg(RO)]` in the register-map definition"
+  `#[reg(RO)]` or `#[reg(RC)]` in the register-map definition, or is part
+  of a nested register map annotated with it"
 )]
 pub trait Writable: Access {}
 
-impl Access for ReadOnly {}
-impl Access for WriteOnly {}
-impl Access for ReadWrite {}
+/// Marker trait for registers supporting read-modify-write operations, such as
+/// [`Reg::modify`](crate::Reg::modify), implemented by type [`ReadWrite`].
+///
+/// ⚠️ This trait is sealed and cannot be implemented for types outside of this crate.
+#[diagnostic::on_unimplemented(
+    message = "cannot read-modify-write a `{Self}` register",
+    label = "method can only be called on read-write registers",
+    note = "writing back the value read from a write-1-to-clear register, annotated with
+  the attribute `#[reg(W1C)]`, would clear all the flags that are set: use
+  `Reg::clear_flags` instead"
+)]
+pub trait Modifiable: Readable + Writable {}
+
+/// Combination of access permissions, used for registers inside of nested register maps.
+///
+/// `<A as Restrict<F>>::Output` is the access permission of a field annotated with `F` (e.g.
+/// `#[reg(RO)]`), in a register map accessed with permission `A`:
+/// - a [`ReadWrite`] map does not restrict its fields, and `Output = F`;
+/// - a [`ReadOnly`] map makes its fields [`ReadOnly`], except [`ReadClear`] fields that stay
+///   [`ReadClear`];
+/// - a [`ReadClear`] map makes its fields [`ReadClear`];
+/// - a [`Write1Clear`] map does not restrict its fields, except [`ReadWrite`] fields that become
+///   [`Write1Clear`];
+/// - [`Write1Clear`] fields are restricted as [`ReadWrite`] fields in any other map.
+/// - a [`WriteOnly`] map makes its fields [`WriteOnly`].
+///
+/// The combination of [`ReadOnly`] or [`ReadClear`] with [`WriteOnly`] is not implemented, as it would result in a
+/// register that can be neither read nor written.
+///
+/// ⚠️ This trait is sealed and cannot be implemented for types outside of this crate.
+#[diagnostic::on_unimplemented(
+    message = "cannot access a `{F}` register through a `{Self}` register map",
+    label = "the register would be neither readable nor writable",
+    note = "read-only and write-only permissions cannot be combined through nested register maps"
+)]
+pub trait Restrict<F: Access>: Access {
+    /// The resulting access permission.
+    type Output: Access;
+}
+
+impl<F: Access> Restrict<F> for ReadWrite {
+    type Output = F;
+}
+impl Restrict<ReadOnly> for ReadOnly {
+    type Output = ReadOnly;
+}
+impl Restrict<ReadWrite> for ReadOnly {
+    type Output = ReadOnly;
+}
+impl Restrict<ReadClear> for ReadOnly {
+    type Output = ReadClear;
+}
+impl Restrict<ReadOnly> for ReadClear {
+    type Output = ReadClear;
+}
+impl Restrict<ReadClear> for ReadClear {
+    type Output = ReadClear;
+}
+impl Restrict<ReadWrite> for ReadClear {
+    type Output = ReadClear;
+}
+impl Restrict<Write1Clear> for ReadOnly {
+    type Output = ReadOnly;
+}
+impl Restrict<Write1Clear> for ReadClear {
+    type Output = ReadClear;
+}
+impl Restrict<Write1Clear> for WriteOnly {
+    type Output = WriteOnly;
+}
+impl Restrict<ReadOnly> for Write1Clear {
+    type Output = ReadOnly;
+}
+impl Restrict<WriteOnly> for Write1Clear {
+    type Output = WriteOnly;
+}
+impl Restrict<ReadWrite> for Write1Clear {
+    type Output = Write1Clear;
+}
+impl Restrict<ReadClear> for Write1Clear {
+    type Output = ReadClear;
+}
+impl Restrict<Write1Clear> for Write1Clear {
+    type Output = Write1Clear;
+}
+impl Restrict<WriteOnly> for WriteOnly {
+    type Output = WriteOnly;
+}
+impl Restrict<ReadWrite> for WriteOnly {
+    type Output = WriteOnly;
+}
+
+impl Access for ReadOnly {
+    const KIND: AccessKind = AccessKind::ReadOnly;
+    const READABLE: bool = true;
+    const WRITABLE: bool = false;
+}
+impl Access for WriteOnly {
+    const KIND: AccessKind = AccessKind::WriteOnly;
+    const READABLE: bool = false;
+    const WRITABLE: bool = true;
+}
+impl Access for ReadClear {
+    const KIND: AccessKind = AccessKind::ReadClear;
+    const READABLE: bool = true;
+    const WRITABLE: bool = false;
+}
+impl Access for Write1Clear {
+    const KIND: AccessKind = AccessKind::Write1Clear;
+    const READABLE: bool = true;
+    const WRITABLE: bool = true;
+}
+impl Access for ReadWrite {
+    const KIND: AccessKind = AccessKind::ReadWrite;
+    const READABLE: bool = true;
+    const WRITABLE: bool = true;
+}
 impl Readable for ReadOnly {}
+impl Readable for ReadClear {}
 impl Readable for ReadWrite {}
 impl Writable for WriteOnly {}
+impl Readable for Write1Clear {}
 impl Writable for ReadWrite {}
+impl Writable for Write1Clear {}
+impl Modifiable for ReadWrite {}
 
 mod private {
     pub trait Sealed {}
     impl Sealed for super::ReadOnly {}
     impl Sealed for super::WriteOnly {}
+    impl Sealed for super::ReadClear {}
     impl Sealed for super::ReadWrite {}
+    impl Sealed for super::Write1Clear {}
 }
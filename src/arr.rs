@@ -1,12 +1,18 @@
-use core::iter::FusedIterator;
+use core::iter::{FusedIterator, Peekable};
 use core::marker::PhantomData;
+use core::mem;
+use core::ops::Range;
 use core::ptr::NonNull;
 
-use crate::access::Access;
+use crate::access::{self, Access};
 use crate::bounds;
-use crate::integers::Integer;
-use crate::iter;
-use crate::reg::{Reg, RegMapPtr};
+use crate::integers::{Integer, RegValue};
+use crate::iter::{RegArrayIter, RegArrayStrideIter};
+#[cfg(feature = "rayon")]
+use crate::par::RegArrayParIter;
+use crate::reg::{Reg, RegMapPtr, RegNonZero};
+use crate::shadow::ShadowArray;
+use crate::slice::RegSlice;
 
 #[cfg(doc)]
 use crate::RegMap;
@@ -33,10 +39,14 @@ impl<'a, P: ArrayElem<'a>, const N: usize> RegArray<'a, P, N> {
     /// - `ptr` must be properly aligned;
     /// - `ptr` must point to `N` contiguous elements of type `P::Target`,
     /// - `ptr` must be valid for the whole lifetime `'a`.
+    ///
+    /// In debug builds, panics if `ptr` is not properly aligned.
     #[doc(hidden)]
     #[allow(non_snake_case)]
     #[inline]
-    pub const unsafe fn __MACRO_ONLY__from_ptr(ptr: *mut [P::Target; N]) -> Self {
+    #[track_caller]
+    pub unsafe fn __MACRO_ONLY__from_ptr(ptr: *mut [P::Target; N]) -> Self {
+        debug_assert!(ptr.is_aligned(), "misaligned register array pointer");
         Self::from_nonnull(NonNull::new_unchecked(ptr))
     }
     #[inline]
@@ -51,6 +61,42 @@ impl<'a, P: ArrayElem<'a>, const N: usize> RegArray<'a, P, N> {
     pub const fn as_ptr(&self) -> *mut [P::Target; N] {
         self.ptr.as_ptr()
     }
+    /// Returns a raw pointer to the underlying array, meant to be handed to a DMA controller.
+    ///
+    /// This is the same pointer returned by [`RegArray::as_ptr`]. See [`Reg::dma_ptr`] for the
+    /// responsibilities of the caller when setting up DMA transfers.
+    #[inline]
+    pub const fn dma_ptr(&self) -> *mut [P::Target; N] {
+        self.as_ptr()
+    }
+    /// Returns `true` if the array is located at address `addr`.
+    #[inline]
+    pub fn eq_addr(&self, addr: *const [P::Target; N]) -> bool {
+        core::ptr::eq(self.as_ptr(), addr)
+    }
+    /// Checks the layout of the array at runtime, for debugging purposes.
+    ///
+    /// Returns `true` if the base address of the array is aligned to the alignment of
+    /// `P::Target`, and if consecutive elements are spaced by the size of `P::Target`.
+    ///
+    /// The address of the second element is obtained by indexing the underlying Rust array in
+    /// place, independently of the pointer arithmetic of [`idx`](Self::idx).
+    #[inline]
+    pub fn debug_check_layout(&self) -> bool {
+        let size = mem::size_of::<P::Target>();
+        let align = mem::align_of::<P::Target>();
+        let base = self.ptr.as_ptr() as usize;
+        // alignment is always a power of two
+        let aligned = base & (align - 1) == 0;
+        let contiguous = mem::size_of::<[P::Target; N]>() == N * size
+            && (N < 2 || {
+                // SAFETY: the pointer is valid for the whole array, and the array has at least two
+                // elements; no reference is created
+                let second = unsafe { core::ptr::addr_of!((*self.ptr.as_ptr())[1]) };
+                second as usize - base == size
+            });
+        aligned && contiguous
+    }
     /// Returns the number of pointers in the array.
     #[allow(clippy::len_without_is_empty)]
     #[inline]
@@ -62,11 +108,102 @@ impl<'a, P: ArrayElem<'a>, const N: usize> RegArray<'a, P, N> {
     /// # Panics
     /// If `index` is out of bounds, i.e. if `index >= N`.
     #[inline]
+    #[track_caller]
     pub fn idx(&self, index: usize) -> P {
         bounds::check_index::<N>(index);
         // SAFETY: we checked i is in bounds
         unsafe { self.idx_unchecked(index) }
     }
+    /// Access the pointer at `index`, or returns `None` if `index` is out of bounds.
+    ///
+    /// This is the non-panicking counterpart of [`RegArray::idx`], similar to [`slice::get`].
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<P> {
+        // SAFETY: we checked i is in bounds
+        (index < N).then(|| unsafe { self.idx_unchecked(index) })
+    }
+    /// Access the pointer at `index`, without doing bounds checking.
+    ///
+    /// Same as [`RegArray::idx_unchecked`], similar to [`slice::get_unchecked`].
+    ///
+    /// # Safety
+    /// `index` must be in bounds: `index < N`.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> P {
+        // SAFETY: the caller promises we are in bounds
+        unsafe { self.idx_unchecked(index) }
+    }
+    /// Access the first pointer of the array.
+    ///
+    /// # Panics
+    /// If the array is empty, i.e. if `N == 0`.
+    #[inline]
+    #[track_caller]
+    pub fn first(&self) -> P {
+        self.get_first().expect("first element of empty array")
+    }
+    /// Access the last pointer of the array.
+    ///
+    /// # Panics
+    /// If the array is empty, i.e. if `N == 0`.
+    #[inline]
+    #[track_caller]
+    pub fn last(&self) -> P {
+        self.get_last().expect("last element of empty array")
+    }
+    /// Access the first pointer of the array, or returns `None` if the array is empty.
+    #[inline]
+    pub fn get_first(&self) -> Option<P> {
+        // SAFETY: the array is not empty
+        (N > 0).then(|| unsafe { self.idx_unchecked(0) })
+    }
+    /// Access the last pointer of the array, or returns `None` if the array is empty.
+    #[inline]
+    pub fn get_last(&self) -> Option<P> {
+        // SAFETY: the array is not empty, so `N - 1` is in bounds
+        (N > 0).then(|| unsafe { self.idx_unchecked(N - 1) })
+    }
+    /// Access the innermost element of a multidimensional array, descending one dimension for
+    /// each index in `indices`.
+    ///
+    /// For an array `[[[u64; 2]; 3]; 5]`, `at(&[i, j, k])` is the same as
+    /// `idx(i).idx(j).idx(k)`.
+    ///
+    /// # Panics
+    /// - If the number of indices differs from the number of dimensions of the array, i.e. if
+    ///   `indices.len() != P::DEPTH + 1`;
+    /// - if any index is out of bounds for its dimension.
+    #[inline]
+    #[track_caller]
+    pub fn at(&self, indices: &[usize]) -> P::Innermost {
+        assert_eq!(
+            indices.len(),
+            P::DEPTH + 1,
+            "number of indices differs from the number of dimensions"
+        );
+        self.idx(indices[0]).descend(&indices[1..])
+    }
+    /// Access the pointer at `index % N`, wrapping around the end of the array.
+    ///
+    /// This is useful e.g. to implement circular buffers. Calling this method on an empty array,
+    /// i.e. with `N == 0`, is a compile-time error:
+    /// ```compile_fail,E0080
+    /// # use reg_map::RegMap;
+    /// #[repr(C)]
+    /// #[derive(RegMap, Default)]
+    /// struct Ring {
+    ///     slots: [u32; 0],
+    /// }
+    /// let mut regs = Ring::default();
+    /// RingPtr::from_mut(&mut regs).slots().idx_wrapping(3);
+    /// ```
+    #[inline]
+    pub fn idx_wrapping(&self, index: usize) -> P {
+        const { assert!(N > 0, "`idx_wrapping` requires a non-empty array") };
+        let index = index % N;
+        // SAFETY: the remainder is always in bounds
+        unsafe { self.idx_unchecked(index) }
+    }
     /// Access the pointer at `index`, without doing bounds checking.
     ///
     /// # Safety
@@ -77,29 +214,461 @@ impl<'a, P: ArrayElem<'a>, const N: usize> RegArray<'a, P, N> {
         // SAFETY: the caller promises we are in bounds
         unsafe { P::from_nonnull(base.add(index)) }
     }
+    /// Returns a view of the array as a dynamically-sized [`RegSlice`].
+    #[inline]
+    pub fn as_slice(&self) -> RegSlice<'a, P> {
+        // SAFETY: the slice covers the same elements as the array
+        unsafe { RegSlice::from_nonnull(self.ptr) }
+    }
     /// Returns an iterator over the pointer array.
-    pub fn iter(
-        &self,
-    ) -> impl 'a + ExactSizeIterator<Item = P> + DoubleEndedIterator + FusedIterator + Clone {
-        iter::RegArrayIter::new(self.ptr)
+    pub fn iter(&self) -> RegArrayIter<'a, P> {
+        RegArrayIter::new(self.ptr)
+    }
+    /// Returns a [`Peekable`] iterator over the pointer array.
+    ///
+    /// This is the same as `self.iter().peekable()`, with a nameable return type that can be
+    /// stored e.g. in the state of a parser looking ahead in an array of registers.
+    #[inline]
+    pub fn peekable_iter(&self) -> Peekable<RegArrayIter<'a, P>> {
+        self.iter().peekable()
+    }
+    /// Returns a parallel iterator over the pointer array, for host-side bulk processing of large
+    /// arrays, e.g. in a simulator.
+    ///
+    /// The index range is split across the threads of the [`rayon`] thread pool, and each task
+    /// constructs the pointer to its element from the base address of the array. See
+    /// [`RegArrayParIter`].
+    ///
+    /// Requires the `rayon` feature.
+    ///
+    /// # Safety
+    /// The registers of the array must be safe to access concurrently from multiple threads, e.g.
+    /// because they are backed by ordinary memory in a simulator, for the whole duration of the
+    /// parallel iteration.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub unsafe fn par_iter(&self) -> RegArrayParIter<'a, P, N> {
+        // SAFETY: the caller promises the array is thread safe
+        unsafe { RegArrayParIter::new(self) }
+    }
+    /// Returns a minimal iterator over the pointer array, for hot loops.
+    ///
+    /// Despite the name, this method is safe: the iterator just strides a raw pointer over the
+    /// `N` elements of the array, and is always in bounds. Compared to [`RegArray::iter`], the
+    /// returned iterator only implements [`Iterator`], without double-ended iteration, exact
+    /// size or fusing guarantees, and without overriding any of the provided `Iterator` methods.
+    #[inline]
+    pub fn iter_unchecked(&self) -> impl 'a + Iterator<Item = P> {
+        RegArrayStrideIter::<'a, P>::new(self.ptr.cast(), N)
     }
     /// Returns an iterator over a subslice `[start..end]` of the pointer array.
     ///
     /// # Panics
     /// If `[start..end]` is out of bounds.
-    pub fn iter_slice(
-        &self,
-        start: usize,
-        end: usize,
-    ) -> impl 'a + ExactSizeIterator<Item = P> + DoubleEndedIterator + FusedIterator + Clone {
+    #[track_caller]
+    pub fn iter_slice(&self, start: usize, end: usize) -> RegArrayIter<'a, P> {
         bounds::check_slice::<N>(start, end);
         let base: NonNull<P::Target> = self.ptr.cast();
         // SAFETY: we checked start..end is in bounds
         unsafe {
             let slice = NonNull::slice_from_raw_parts(base.add(start), end - start);
-            iter::RegArrayIter::new(slice)
+            RegArrayIter::new(slice)
         }
     }
+    /// Returns an iterator over a subslice of the pointer array, never panicking.
+    ///
+    /// Both `start` and `end` are first clamped to `N`, and then swapped if `start > end`. The
+    /// iterator is empty if both are clamped to the same value.
+    pub fn iter_slice_clamped(&self, start: usize, end: usize) -> RegArrayIter<'a, P> {
+        let start = start.min(N);
+        let end = end.min(N);
+        if start <= end {
+            self.iter_slice(start, end)
+        } else {
+            self.iter_slice(end, start)
+        }
+    }
+    /// Returns an iterator over a subslice `[start..end]` of the pointer array, together with both
+    /// the relative and absolute indices of each pointer.
+    ///
+    /// The iterator yields tuples `(rel, abs, ptr)`, where `rel` is the index relative to `start`,
+    /// and `abs = start + rel` is the index in the whole array.
+    ///
+    /// # Panics
+    /// If `[start..end]` is out of bounds.
+    #[track_caller]
+    pub fn iter_slice_enumerated(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> impl 'a
+           + ExactSizeIterator<Item = (usize, usize, P)>
+           + DoubleEndedIterator
+           + FusedIterator
+           + Clone {
+        self.iter_slice(start, end)
+            .enumerate()
+            .map(move |(rel, ptr)| (rel, start + rel, ptr))
+    }
+    /// Returns an iterator over a subslice `[start..end]` of the pointer array, together with the
+    /// absolute byte address of each pointer.
+    ///
+    /// The iterator yields tuples `(addr, ptr)`, where `addr` is the same as
+    /// `ptr.as_ptr() as usize`. Addresses increase by `size_of::<P::Target>()` bytes from one
+    /// element to the next. This is useful e.g. to generate DMA descriptors.
+    ///
+    /// # Panics
+    /// If `[start..end]` is out of bounds.
+    #[track_caller]
+    pub fn iter_slice_addrs(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> impl 'a + ExactSizeIterator<Item = (usize, P)> + DoubleEndedIterator + FusedIterator + Clone
+    {
+        let base = self.as_ptr() as usize;
+        let size = mem::size_of::<P::Target>();
+        self.iter_slice(start, end)
+            .enumerate()
+            .map(move |(rel, ptr)| (base + (start + rel) * size, ptr))
+    }
+    /// Returns an iterator over pairs of adjacent pointers in the array.
+    ///
+    /// The iterator yields `(self.idx(i), self.idx(i + 1))` for `i` in `0..N-1`, and is empty if
+    /// `N < 2`. No reads are performed by the iterator itself.
+    pub fn pairwise(
+        &self,
+    ) -> impl 'a + ExactSizeIterator<Item = (P, P)> + DoubleEndedIterator + FusedIterator + Clone
+    {
+        self.iter().zip(self.iter().skip(1))
+    }
+    /// Divides the array into two [`RegSlice`]s at index `mid`.
+    ///
+    /// The first slice covers the indices `[0..mid]`, and the second `[mid..N]`. Both slices
+    /// point to the registers of `self`, and can be used at the same time with the same
+    /// lifetime `'a`.
+    ///
+    /// # Panics
+    /// If `mid > N`.
+    #[inline]
+    #[track_caller]
+    pub fn split_at(&self, mid: usize) -> (RegSlice<'a, P>, RegSlice<'a, P>) {
+        bounds::check_slice::<N>(mid, N);
+        let base: NonNull<P::Target> = self.ptr.cast();
+        // SAFETY: we checked `mid <= N`, so both `[0..mid]` and `[mid..N]` are in bounds
+        unsafe {
+            let head = NonNull::slice_from_raw_parts(base, mid);
+            let tail = NonNull::slice_from_raw_parts(base.add(mid), N - mid);
+            (RegSlice::from_nonnull(head), RegSlice::from_nonnull(tail))
+        }
+    }
+    /// Returns an iterator over all the overlapping windows of `size` elements of the array, as
+    /// [`RegSlice`]s.
+    ///
+    /// The windows start at each index in `0..=N - size`, like for [`slice::windows`]. The iterator
+    /// is empty if `size > N`. No reads are performed by the iterator itself.
+    ///
+    /// # Panics
+    /// If `size` is zero.
+    #[track_caller]
+    pub fn windows(
+        &self,
+        size: usize,
+    ) -> impl 'a + ExactSizeIterator<Item = RegSlice<'a, P>> + DoubleEndedIterator + FusedIterator
+    {
+        assert!(size != 0, "window size must be non-zero");
+        let base: NonNull<P::Target> = self.ptr.cast();
+        (0..(N + 1).saturating_sub(size)).map(move |start| {
+            // SAFETY: `[start..start + size]` is in bounds, since `start + size <= N`
+            unsafe {
+                let slice = NonNull::slice_from_raw_parts(base.add(start), size);
+                RegSlice::from_nonnull(slice)
+            }
+        })
+    }
+    /// Returns an iterator over chunks of `size` elements of the array, as [`RegSlice`]s.
+    ///
+    /// The chunks do not overlap. If `size` does not divide `N`, the last chunk is shorter, like
+    /// for [`slice::chunks`]. No reads are performed by the iterator itself.
+    ///
+    /// # Panics
+    /// If `size` is zero.
+    #[track_caller]
+    pub fn chunks(
+        &self,
+        size: usize,
+    ) -> impl 'a + ExactSizeIterator<Item = RegSlice<'a, P>> + DoubleEndedIterator + FusedIterator
+    {
+        assert!(size != 0, "chunk size must be non-zero");
+        let base: NonNull<P::Target> = self.ptr.cast();
+        (0..N.div_ceil(size)).map(move |i| {
+            let start = i * size;
+            let end = (start + size).min(N);
+            // SAFETY: `[start..end]` is in bounds, since `start < N` and `end <= N`
+            unsafe {
+                let slice = NonNull::slice_from_raw_parts(base.add(start), end - start);
+                RegSlice::from_nonnull(slice)
+            }
+        })
+    }
+}
+
+// arrays of basic registers
+impl<'a, T: RegValue + 'a, A: Access, const N: usize> RegArray<'a, Reg<'a, T, A>, N> {
+    /// Copies the elements in the range `src` to the elements starting at index `dest`.
+    ///
+    /// Each element is copied with one volatile read and one volatile write. The source and
+    /// destination ranges can overlap: the copy is performed front-to-back when moving elements
+    /// towards the start of the array, and back-to-front otherwise.
+    ///
+    /// # Panics
+    /// If `src` is out of bounds, or if `dest + src.len()` is larger than `N`.
+    #[track_caller]
+    pub fn copy_within(&self, src: Range<usize>, dest: usize)
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        let Range { start, end } = src;
+        bounds::check_slice::<N>(start, end);
+        let count = end - start;
+        match dest.checked_add(count) {
+            Some(dest_end) => bounds::check_slice::<N>(dest, dest_end),
+            None => panic!("range end index out of range for slice of length {N}"),
+        }
+        // SAFETY: we checked both ranges are in bounds
+        unsafe {
+            if dest <= start {
+                for i in 0..count {
+                    self.idx_unchecked(dest + i)
+                        .write(self.idx_unchecked(start + i).read());
+                }
+            } else {
+                for i in (0..count).rev() {
+                    self.idx_unchecked(dest + i)
+                        .write(self.idx_unchecked(start + i).read());
+                }
+            }
+        }
+    }
+    /// Pairs the array with a shadow buffer of the last-written values, see [`ShadowArray`].
+    ///
+    /// The shadow buffer is not initialized from the registers: use
+    /// [`ShadowArray::refresh`] or write all the elements first.
+    ///
+    /// # Panics
+    /// If the length of `shadow` is not `N`.
+    #[track_caller]
+    pub fn with_shadow<'s>(&self, shadow: &'s mut [T]) -> ShadowArray<'a, 's, T, A, N> {
+        // SAFETY: same pointer as `self`, with the same lifetime
+        ShadowArray::new(unsafe { RegArray::from_nonnull(self.ptr) }, shadow)
+    }
+    /// Returns an iterator that reads the elements in order, and yields the result of `f` applied
+    /// to each value read.
+    ///
+    /// Each element is read lazily, when the iterator is advanced. This is useful to decode arrays
+    /// of raw integers into a typed value, e.g. fixed-point numbers.
+    #[inline]
+    pub fn read_iter_map<U, F>(
+        &self,
+        mut f: F,
+    ) -> impl 'a + ExactSizeIterator<Item = U> + DoubleEndedIterator + FusedIterator
+    where
+        A: access::Readable,
+        F: 'a + FnMut(T) -> U,
+    {
+        self.iter().map(move |reg| f(reg.read()))
+    }
+    /// Reads all the elements in order, and extends the collection `c` with the values read.
+    ///
+    /// This is convenient to dump an array into e.g. a `Vec` or a `VecDeque`.
+    #[inline]
+    pub fn extend_into<C: Extend<T>>(&self, c: &mut C)
+    where
+        A: access::Readable,
+    {
+        c.extend(self.iter().map(|reg| reg.read()));
+    }
+    /// Reads all the elements in order, and returns them as an array.
+    pub fn read_array(&self) -> [T; N]
+    where
+        A: access::Readable,
+    {
+        // SAFETY: `from_fn` calls the closure with indices in `0..N`
+        core::array::from_fn(|i| unsafe { self.idx_unchecked(i) }.read())
+    }
+    /// Writes all the elements in order, from the values in `vals`.
+    pub fn write_array(&self, vals: [T; N])
+    where
+        A: access::Writable,
+    {
+        for (reg, val) in self.iter().zip(vals) {
+            reg.write(val);
+        }
+    }
+    /// Reads all the elements in order into `out`.
+    ///
+    /// Each element is read with a separate volatile read: unlike a `memcpy`, the accesses are
+    /// never merged or reordered.
+    ///
+    /// # Panics
+    /// If the length of `out` is not `N`.
+    #[track_caller]
+    pub fn read_all(&self, out: &mut [T])
+    where
+        A: access::Readable,
+    {
+        assert_eq!(out.len(), N, "buffer length must match the array length");
+        for (reg, val) in self.iter().zip(out) {
+            *val = reg.read();
+        }
+    }
+    /// Writes all the elements in order, from the values in `src`.
+    ///
+    /// Each element is written with a separate volatile write: unlike a `memcpy`, the accesses
+    /// are never merged or reordered.
+    ///
+    /// # Panics
+    /// If the length of `src` is not `N`.
+    #[track_caller]
+    pub fn write_all(&self, src: &[T])
+    where
+        A: access::Writable,
+    {
+        assert_eq!(src.len(), N, "buffer length must match the array length");
+        for (reg, &val) in self.iter().zip(src) {
+            reg.write(val);
+        }
+    }
+    /// Writes `value` to all the elements.
+    ///
+    /// The elements are written in order, front-to-back, with one volatile write each.
+    pub fn fill(&self, value: T)
+    where
+        A: access::Writable,
+    {
+        for reg in self.iter() {
+            reg.write(value);
+        }
+    }
+    /// Writes to each element the value returned by `f`, called with the index of the element.
+    ///
+    /// The elements are written in order, front-to-back, with one volatile write each.
+    pub fn fill_with<F: FnMut(usize) -> T>(&self, mut f: F)
+    where
+        A: access::Writable,
+    {
+        for (i, reg) in self.iter().enumerate() {
+            reg.write(f(i));
+        }
+    }
+    /// Reads each element once, and writes zero to the elements whose value does not satisfy the
+    /// predicate `f`.
+    ///
+    /// Zero is the value whose bits are all cleared, i.e. `T::from_bits(0)`. Elements satisfying
+    /// the predicate are not written.
+    ///
+    /// ⚠️ Each element is updated with a separate volatile read and volatile write: this operation
+    /// is *not* atomic.
+    pub fn clear_unless<F: Fn(T) -> bool>(&self, f: F)
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        for reg in self.iter() {
+            if !f(reg.read()) {
+                reg.write(T::from_bits(Integer::ZERO));
+            }
+        }
+    }
+    /// Reads each element once, and returns the minimum value.
+    ///
+    /// Returns `None` if the array is empty. If several elements are equally minimum, the value
+    /// of the first one is returned.
+    pub fn read_min(&self) -> Option<T>
+    where
+        T: Ord,
+        A: access::Readable,
+    {
+        self.iter().map(|reg| reg.read()).min()
+    }
+    /// Reads each element once, and returns the maximum value.
+    ///
+    /// Returns `None` if the array is empty. If several elements are equally maximum, the value
+    /// of the last one is returned.
+    pub fn read_max(&self) -> Option<T>
+    where
+        T: Ord,
+        A: access::Readable,
+    {
+        self.iter().map(|reg| reg.read()).max()
+    }
+    /// Returns the index of the partition point according to the given predicate, i.e. the index
+    /// of the first element for which `pred` returns `false`.
+    ///
+    /// The array is assumed to be partitioned according to `pred`: all the elements for which
+    /// `pred` returns `true` are at the start of the array, and all the elements for which `pred`
+    /// returns `false` are at the end. E.g. for a sorted array, `pred` can be `|x| x < value`. If
+    /// the array is not partitioned, the returned index is unspecified but in bounds.
+    ///
+    /// The search is a binary search, performing one volatile read per step. See also
+    /// [`slice::partition_point`].
+    pub fn partition_point<F: Fn(T) -> bool>(&self, pred: F) -> usize
+    where
+        A: access::Readable,
+    {
+        let mut lo = 0;
+        let mut hi = N;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            // SAFETY: lo <= mid < hi <= N
+            if pred(unsafe { self.idx_unchecked(mid) }.read()) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+}
+
+// two-dimensional arrays
+impl<'a, P: ArrayElem<'a>, const C: usize, const R: usize> RegArray<'a, RegArray<'a, P, C>, R> {
+    /// Access the pointer at row `i` and column `j`.
+    ///
+    /// Same as `self.idx(i).idx(j)`, but computing the address of the element directly.
+    ///
+    /// # Panics
+    /// If `i >= R` or `j >= C`.
+    #[inline]
+    #[track_caller]
+    pub fn idx2(&self, i: usize, j: usize) -> P {
+        bounds::check_index::<R>(i);
+        bounds::check_index::<C>(j);
+        let base: NonNull<P::Target> = self.ptr.cast();
+        // SAFETY: we checked both indices are in bounds, and the rows are contiguous
+        unsafe { P::from_nonnull(base.add(i * C + j)) }
+    }
+}
+
+// three-dimensional arrays
+impl<'a, P: ArrayElem<'a>, const C2: usize, const C1: usize, const R: usize>
+    RegArray<'a, RegArray<'a, RegArray<'a, P, C2>, C1>, R>
+{
+    /// Access the pointer at indices `i`, `j` and `k`.
+    ///
+    /// Same as `self.idx(i).idx(j).idx(k)`, but computing the address of the element directly.
+    ///
+    /// # Panics
+    /// If `i >= R`, `j >= C1` or `k >= C2`.
+    #[inline]
+    #[track_caller]
+    pub fn idx3(&self, i: usize, j: usize, k: usize) -> P {
+        bounds::check_index::<R>(i);
+        bounds::check_index::<C1>(j);
+        bounds::check_index::<C2>(k);
+        let base: NonNull<P::Target> = self.ptr.cast();
+        // SAFETY: we checked all indices are in bounds, and the sub-arrays are contiguous
+        unsafe { P::from_nonnull(base.add((i * C1 + j) * C2 + k)) }
+    }
 }
 
 /// Types that can be stored in a [`RegArray`].
@@ -115,15 +684,69 @@ pub trait ArrayElem<'a>: 'a + private::Sealed {
     /// - `ptr` must point to a valid instance of `Self::Target`;
     /// - `ptr` must be valid for the whole lifetime `'a`.
     unsafe fn from_nonnull(ptr: NonNull<Self::Target>) -> Self;
+
+    /// Returns a raw pointer to the underlying `Self::Target`.
+    fn as_ptr(&self) -> *mut Self::Target;
+
+    /// The innermost element type: `Self` for registers and register maps, the innermost element
+    /// type of the nested arrays for multidimensional arrays.
+    type Innermost: ArrayElem<'a>;
+
+    /// The number of array dimensions of `Self`: `0` for registers and register maps.
+    const DEPTH: usize;
+
+    /// Descends one array dimension for each index in `indices`, see [`RegArray::at`].
+    ///
+    /// # Panics
+    /// If `indices.len() != Self::DEPTH`, or if any index is out of bounds.
+    #[track_caller]
+    fn descend(self, indices: &[usize]) -> Self::Innermost;
 }
 
 // arrays of basic registers
-impl<'a, T: Integer, A: Access> ArrayElem<'a> for Reg<'a, T, A> {
-    type Target = T;
+impl<'a, T: RegValue + 'a, A: Access> ArrayElem<'a> for Reg<'a, T, A> {
+    type Target = T::Bits;
 
     unsafe fn from_nonnull(ptr: NonNull<Self::Target>) -> Self {
         Reg::from_nonnull(ptr)
     }
+
+    fn as_ptr(&self) -> *mut Self::Target {
+        Reg::as_ptr(self)
+    }
+
+    type Innermost = Self;
+
+    const DEPTH: usize = 0;
+
+    #[track_caller]
+    fn descend(self, indices: &[usize]) -> Self::Innermost {
+        assert!(indices.is_empty(), "too many indices for array");
+        self
+    }
+}
+
+// arrays of non-zero registers
+impl<'a, T: Integer, A: Access> ArrayElem<'a> for RegNonZero<'a, T, A> {
+    type Target = T;
+
+    unsafe fn from_nonnull(ptr: NonNull<Self::Target>) -> Self {
+        RegNonZero::from_nonnull(ptr)
+    }
+
+    fn as_ptr(&self) -> *mut Self::Target {
+        RegNonZero::as_ptr(self)
+    }
+
+    type Innermost = Self;
+
+    const DEPTH: usize = 0;
+
+    #[track_caller]
+    fn descend(self, indices: &[usize]) -> Self::Innermost {
+        assert!(indices.is_empty(), "too many indices for array");
+        self
+    }
 }
 
 // arrays of custom register maps (structs)
@@ -133,6 +756,20 @@ impl<'a, T: RegMapPtr<'a>> ArrayElem<'a> for T {
     unsafe fn from_nonnull(ptr: NonNull<Self::Target>) -> Self {
         T::from_nonnull(ptr)
     }
+
+    fn as_ptr(&self) -> *mut Self::Target {
+        RegMapPtr::as_ptr(self)
+    }
+
+    type Innermost = Self;
+
+    const DEPTH: usize = 0;
+
+    #[track_caller]
+    fn descend(self, indices: &[usize]) -> Self::Innermost {
+        assert!(indices.is_empty(), "too many indices for array");
+        self
+    }
 }
 
 // multidimensional arrays
@@ -142,16 +779,51 @@ impl<'a, T: ArrayElem<'a>, const N: usize> ArrayElem<'a> for RegArray<'a, T, N>
     unsafe fn from_nonnull(ptr: NonNull<Self::Target>) -> Self {
         RegArray::from_nonnull(ptr)
     }
+
+    fn as_ptr(&self) -> *mut Self::Target {
+        RegArray::as_ptr(self)
+    }
+
+    type Innermost = T::Innermost;
+
+    const DEPTH: usize = T::DEPTH + 1;
+
+    #[track_caller]
+    fn descend(self, indices: &[usize]) -> Self::Innermost {
+        let (&first, rest) = indices.split_first().expect("too few indices for array");
+        self.idx(first).descend(rest)
+    }
+}
+
+/// Names the element types of a [`RegArray`] in generic code.
+///
+/// This trait is implemented by all `RegArray`s, and plays the role of associated type aliases
+/// on `RegArray` itself, e.g. `<RegArray<'a, P, N> as ArrayTypes<'a>>::Elem` is `P`.
+///
+/// ⚠️ This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait ArrayTypes<'a>: private::Sealed {
+    /// The type of the pointers stored in the array, e.g. a [`Reg`].
+    type Elem: ArrayElem<'a, Target = Self::Target>;
+
+    /// The target type of the pointers stored in the array, i.e. the element type of the
+    /// underlying Rust array.
+    type Target;
+}
+
+impl<'a, P: ArrayElem<'a>, const N: usize> ArrayTypes<'a> for RegArray<'a, P, N> {
+    type Elem = P;
+    type Target = P::Target;
 }
 
 mod private {
     use crate::access::Access;
     use crate::arr::{ArrayElem, RegArray};
-    use crate::integers::Integer;
-    use crate::reg::{Reg, RegMapPtr};
+    use crate::integers::{Integer, RegValue};
+    use crate::reg::{Reg, RegMapPtr, RegNonZero};
 
     pub trait Sealed {}
-    impl<'a, T: Integer, A: Access> Sealed for Reg<'a, T, A> {}
+    impl<'a, T: RegValue, A: Access> Sealed for Reg<'a, T, A> {}
+    impl<'a, T: Integer, A: Access> Sealed for RegNonZero<'a, T, A> {}
     impl<'a, T: RegMapPtr<'a>> Sealed for T {}
     impl<'a, T: ArrayElem<'a>, const N: usize> Sealed for RegArray<'a, T, N> {}
 }
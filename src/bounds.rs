@@ -6,6 +6,7 @@
 ///
 /// If `index` is out of bounds.
 #[inline]
+#[track_caller]
 pub(crate) const fn check_index<const LEN: usize>(index: usize) {
     let max_array = [(); LEN];
     let _ = &max_array[index];
@@ -19,7 +20,42 @@ pub(crate) const fn check_index<const LEN: usize>(index: usize) {
 ///
 /// If `[start..end]` is out of bounds.
 #[inline]
+#[track_caller]
 pub(crate) fn check_slice<const LEN: usize>(start: usize, end: usize) {
     let max_array = [(); LEN];
     let _ = &max_array[start..end];
 }
+
+/// Utility function to check if `index` is in bounds for a slice of length `len`.
+///
+/// Does nothing on success.
+///
+/// # Panics
+///
+/// If `index` is out of bounds.
+#[inline]
+#[track_caller]
+pub(crate) fn check_index_len(index: usize, len: usize) {
+    let _ = &unit_slice(len)[index];
+}
+
+/// Utility function to check if `[start..end]` is in bounds for a slice of length `len`.
+///
+/// Does nothing on success.
+///
+/// # Panics
+///
+/// If `[start..end]` is out of bounds.
+#[inline]
+#[track_caller]
+pub(crate) fn check_slice_len(start: usize, end: usize, len: usize) {
+    let _ = &unit_slice(len)[start..end];
+}
+
+/// A slice of `len` zero-sized elements, so that indexing it panics with the same messages as
+/// indexing a slice of length `len`.
+#[inline]
+fn unit_slice(len: usize) -> &'static [()] {
+    // SAFETY: a dangling pointer is valid for any number of zero-sized elements
+    unsafe { core::slice::from_raw_parts(core::ptr::NonNull::dangling().as_ptr(), len) }
+}
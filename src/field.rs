@@ -0,0 +1,30 @@
+//! Runtime information about the registers of a register map.
+
+use crate::access::AccessKind;
+
+#[cfg(doc)]
+use crate::RegMap;
+
+/// Runtime information about a register of a register map.
+///
+/// With the `#[reg_map(fields)]` attribute on the register map, the pointer derived by
+/// [`RegMap`] provides a method `fields()` that returns an iterator over the `FieldInfo` of all
+/// the fields of integer, non-zero integer, boolean or floating-point type in the register map,
+/// in declaration order. Arrays and nested register maps are not included. The method is opt-in
+/// so that it does not collide with a field named `fields`.
+///
+/// This is useful e.g. to implement register browsers and other debugging tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldInfo {
+    /// The name of the field in the register map.
+    pub name: &'static str,
+    /// The address of the register.
+    pub addr: usize,
+    /// The width of the register in bits.
+    pub width: usize,
+    /// The access permission of the register.
+    ///
+    /// For nested register maps, this includes the permission propagated from the outer map, see
+    /// [Nested register maps](crate#nested-register-maps-1) in the access permissions.
+    pub access: AccessKind,
+}
@@ -2,6 +2,63 @@
 
 use core::fmt::Debug;
 use core::hash::Hash;
+#[cfg(feature = "use-atomics")]
+use core::mem;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8,
+};
+use core::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+use core::ptr::NonNull;
+#[cfg(feature = "use-atomics")]
+use core::sync::atomic::Ordering;
+
+#[cfg(doc)]
+use crate::RegMap;
+
+/// Types that can be read from and written to a [`Reg`](crate::reg::Reg).
+///
+/// A register always stores an [`Integer`], of type [`RegValue::Bits`]. Implementing this trait
+/// for a custom type defines how the custom type is converted from and to the stored integer, so
+/// that [`read`](crate::reg::Reg::read) and [`write`](crate::reg::Reg::write) can operate on the
+/// custom type directly.
+///
+/// This trait is implemented on all [`Integer`] types, with `Bits = Self` and trivial conversions.
+/// It is also implemented on [`bool`] with `Bits = u8`, where any non-zero value is converted to `true`, see
+/// [Boolean registers](crate#boolean-registers), and on [`f32`] and [`f64`] with `Bits = u32`
+/// and `u64`, see [Floating-point registers](crate#floating-point-registers).
+///
+/// Custom types are used in a register map with the `#[reg(via = Type)]` attribute of the derive
+/// macro [`RegMap`], see [Custom register values](crate#custom-register-values) in the crate
+/// documentation.
+pub trait RegValue: Copy {
+    /// The integer type stored in the register.
+    type Bits: Integer;
+
+    /// Converts the integer stored in the register to `Self`.
+    fn from_bits(bits: Self::Bits) -> Self;
+
+    /// Converts `self` to the integer stored in the register.
+    fn to_bits(self) -> Self::Bits;
+}
+
+/// C-style enums stored in an integer register.
+///
+/// Unlike [`RegValue`], the conversion from the stored integer can fail: registers holding an
+/// enum are read with [`Reg::read_enum`](crate::reg::Reg::read_enum), which returns `None` if the
+/// hardware returns a value that does not correspond to any variant. Variants are written with
+/// [`Reg::write_enum`](crate::reg::Reg::write_enum).
+pub trait RegEnum: Sized {
+    /// The integer type stored in the register.
+    type Repr: Integer;
+
+    /// Converts the integer stored in the register to `Self`, or returns `None` if `repr` does
+    /// not correspond to any variant.
+    fn from_repr(repr: Self::Repr) -> Option<Self>;
+
+    /// Converts `self` to the integer stored in the register.
+    fn to_repr(&self) -> Self::Repr;
+}
 
 /// Types that can be placed into a [`Reg`](crate::reg::Reg).
 ///
@@ -10,31 +67,407 @@ use core::hash::Hash;
 ///
 /// ⚠️ This trait is sealed and cannot be implemented for types outside of this crate.
 pub trait Integer:
-    Debug + Default + Copy + Eq + Ord + Hash + Sized + Send + Sync + 'static + private::Sealed
+    RegValue<Bits = Self>
+    + Debug
+    + Default
+    + Copy
+    + Eq
+    + Ord
+    + Hash
+    + Sized
+    + Send
+    + Sync
+    + 'static
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+    + private::Sealed
 {
+    /// The non-zero counterpart of this integer type, e.g. [`NonZeroU32`] for [`u32`].
+    type NonZero: TryFrom<Self>
+        + Into<Self>
+        + Debug
+        + Copy
+        + Eq
+        + Ord
+        + Hash
+        + Send
+        + Sync
+        + 'static;
+
+    /// The value `0`.
+    const ZERO: Self;
+
+    /// The value `1`.
+    const ONE: Self;
+
+    /// The size of this integer type in bits.
+    ///
+    /// See e.g. [`u32::BITS`].
+    const BITS: u32;
+
+    /// Returns the number of ones in the binary representation of `self`.
+    ///
+    /// See e.g. [`u32::count_ones`].
+    fn count_ones(self) -> u32;
+
+    /// Returns the number of trailing zeros in the binary representation of `self`.
+    ///
+    /// See e.g. [`u32::trailing_zeros`].
+    fn trailing_zeros(self) -> u32;
+
+    /// Returns the number of leading zeros in the binary representation of `self`.
+    ///
+    /// See e.g. [`u32::leading_zeros`].
+    fn leading_zeros(self) -> u32;
 }
 
-impl Integer for u8 {}
-impl Integer for u16 {}
-impl Integer for u32 {}
-impl Integer for u64 {}
-impl Integer for u128 {}
-impl Integer for i8 {}
-impl Integer for i16 {}
-impl Integer for i32 {}
-impl Integer for i64 {}
-impl Integer for i128 {}
-
-mod private {
-    pub trait Sealed {}
-    impl Sealed for u8 {}
-    impl Sealed for u16 {}
-    impl Sealed for u32 {}
-    impl Sealed for u64 {}
-    impl Sealed for u128 {}
-    impl Sealed for i8 {}
-    impl Sealed for i16 {}
-    impl Sealed for i32 {}
-    impl Sealed for i64 {}
-    impl Sealed for i128 {}
+macro_rules! impl_integer {
+    ($($ty:ty => $nz:ty $(, $atomic:ty, $width:literal)?;)*) => {
+        $(
+            impl RegValue for $ty {
+                type Bits = Self;
+
+                #[inline(always)]
+                fn from_bits(bits: Self::Bits) -> Self {
+                    bits
+                }
+
+                #[inline(always)]
+                fn to_bits(self) -> Self::Bits {
+                    self
+                }
+            }
+
+            impl Integer for $ty {
+                type NonZero = $nz;
+
+                const ZERO: Self = 0;
+
+                const ONE: Self = 1;
+
+                const BITS: u32 = <$ty>::BITS;
+
+                #[inline(always)]
+                fn count_ones(self) -> u32 {
+                    <$ty>::count_ones(self)
+                }
+
+                #[inline(always)]
+                fn trailing_zeros(self) -> u32 {
+                    <$ty>::trailing_zeros(self)
+                }
+
+                #[inline(always)]
+                fn leading_zeros(self) -> u32 {
+                    <$ty>::leading_zeros(self)
+                }
+            }
+
+            impl private::Sealed for $ty {
+                #[inline(always)]
+                unsafe fn load(ptr: NonNull<Self>) -> Self {
+                    $(
+                        #[cfg(all(feature = "use-atomics", target_has_atomic = $width))]
+                        if mem::align_of::<$atomic>() == mem::align_of::<Self>() {
+                            // SAFETY: the caller promises the pointer is valid for reads, and we
+                            // checked the alignment
+                            return unsafe { <$atomic>::from_ptr(ptr.as_ptr()) }
+                                .load(core::sync::atomic::Ordering::Relaxed);
+                        }
+                    )?
+                    // SAFETY: the caller promises the pointer is valid for reads
+                    unsafe { core::ptr::read_volatile(ptr.as_ptr()) }
+                }
+
+                #[inline(always)]
+                unsafe fn store(ptr: NonNull<Self>, val: Self) {
+                    $(
+                        #[cfg(all(feature = "use-atomics", target_has_atomic = $width))]
+                        if mem::align_of::<$atomic>() == mem::align_of::<Self>() {
+                            // SAFETY: the caller promises the pointer is valid for writes, and we
+                            // checked the alignment
+                            return unsafe { <$atomic>::from_ptr(ptr.as_ptr()) }
+                                .store(val, core::sync::atomic::Ordering::Relaxed);
+                        }
+                    )?
+                    // SAFETY: the caller promises the pointer is valid for writes
+                    unsafe { core::ptr::write_volatile(ptr.as_ptr(), val) }
+                }
+
+                #[inline(always)]
+                unsafe fn fetch_update<F: FnMut(Self) -> Option<Self>>(
+                    ptr: NonNull<Self>,
+                    mut f: F,
+                ) -> Result<Self, Self> {
+                    $(
+                        #[cfg(all(feature = "use-atomics", target_has_atomic = $width))]
+                        if mem::align_of::<$atomic>() == mem::align_of::<Self>() {
+                            // SAFETY: the caller promises the pointer is valid for reads and
+                            // writes, and we checked the alignment
+                            return unsafe { <$atomic>::from_ptr(ptr.as_ptr()) }.fetch_update(
+                                core::sync::atomic::Ordering::Relaxed,
+                                core::sync::atomic::Ordering::Relaxed,
+                                f,
+                            );
+                        }
+                    )?
+                    // SAFETY: the caller promises the pointer is valid for reads and writes
+                    let old = unsafe { Self::load(ptr) };
+                    match f(old) {
+                        Some(new) => {
+                            // SAFETY: as above
+                            unsafe { Self::store(ptr, new) };
+                            Ok(old)
+                        }
+                        None => Err(old),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(
+    u8 => NonZeroU8, core::sync::atomic::AtomicU8, "8";
+    u16 => NonZeroU16, core::sync::atomic::AtomicU16, "16";
+    u32 => NonZeroU32, core::sync::atomic::AtomicU32, "32";
+    u64 => NonZeroU64, core::sync::atomic::AtomicU64, "64";
+    u128 => NonZeroU128;
+    i8 => NonZeroI8, core::sync::atomic::AtomicI8, "8";
+    i16 => NonZeroI16, core::sync::atomic::AtomicI16, "16";
+    i32 => NonZeroI32, core::sync::atomic::AtomicI32, "32";
+    i64 => NonZeroI64, core::sync::atomic::AtomicI64, "64";
+    i128 => NonZeroI128;
+);
+
+macro_rules! impl_float {
+    ($($ty:ty => $bits:ty;)*) => {
+        $(
+            impl RegValue for $ty {
+                type Bits = $bits;
+
+                #[inline(always)]
+                fn from_bits(bits: Self::Bits) -> Self {
+                    <$ty>::from_bits(bits)
+                }
+
+                #[inline(always)]
+                fn to_bits(self) -> Self::Bits {
+                    <$ty>::to_bits(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(
+    f32 => u32;
+    f64 => u64;
+);
+
+impl RegValue for bool {
+    type Bits = u8;
+
+    #[inline(always)]
+    fn from_bits(bits: Self::Bits) -> Self {
+        bits != 0
+    }
+
+    #[inline(always)]
+    fn to_bits(self) -> Self::Bits {
+        self as u8
+    }
+}
+
+/// Integer types that support atomic operations on a [`Reg`](crate::reg::Reg), with the
+/// `use-atomics` feature.
+///
+/// This trait is implemented on all [`Integer`] types that have a matching atomic type on the
+/// target, e.g. [`AtomicU32`](core::sync::atomic::AtomicU32) for [`u32`].
+///
+/// ⚠️ This trait is sealed and cannot be implemented for types outside of this crate.
+#[cfg(feature = "use-atomics")]
+pub trait AtomicInteger: Integer + private::SealedAtomic {}
+
+#[cfg(feature = "use-atomics")]
+macro_rules! impl_atomic_integer {
+    ($($ty:ty => $atomic:ty, $width:literal;)*) => {
+        $(
+            #[cfg(target_has_atomic = $width)]
+            impl AtomicInteger for $ty {}
+
+            #[cfg(target_has_atomic = $width)]
+            impl private::SealedAtomic for $ty {
+                type Atomic = $atomic;
+
+                #[inline(always)]
+                unsafe fn as_atomic<'a>(ptr: NonNull<Self>) -> &'a Self::Atomic {
+                    let align = mem::align_of::<Self::Atomic>();
+                    assert!(
+                        align == mem::align_of::<Self>() || ptr.as_ptr() as usize & (align - 1) == 0,
+                        "register is not aligned for atomic access"
+                    );
+                    // SAFETY: the caller promises the pointer is valid, and we checked the
+                    // alignment
+                    unsafe { <$atomic>::from_ptr(ptr.as_ptr()) }
+                }
+
+                #[inline(always)]
+                unsafe fn atomic_load(ptr: NonNull<Self>, order: Ordering) -> Self {
+                    // SAFETY: the caller promises the pointer is valid for reads
+                    unsafe { Self::as_atomic(ptr) }.load(order)
+                }
+
+                #[inline(always)]
+                unsafe fn atomic_store(ptr: NonNull<Self>, val: Self, order: Ordering) {
+                    // SAFETY: the caller promises the pointer is valid for writes
+                    unsafe { Self::as_atomic(ptr) }.store(val, order)
+                }
+
+                #[inline(always)]
+                unsafe fn compare_exchange(
+                    ptr: NonNull<Self>,
+                    current: Self,
+                    new: Self,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<Self, Self> {
+                    // SAFETY: the caller promises the pointer is valid for reads and writes
+                    unsafe { Self::as_atomic(ptr) }.compare_exchange(current, new, success, failure)
+                }
+
+                #[inline(always)]
+                unsafe fn fetch_or(ptr: NonNull<Self>, val: Self, order: Ordering) -> Self {
+                    // SAFETY: the caller promises the pointer is valid for reads and writes
+                    unsafe { Self::as_atomic(ptr) }.fetch_or(val, order)
+                }
+
+                #[inline(always)]
+                unsafe fn fetch_and(ptr: NonNull<Self>, val: Self, order: Ordering) -> Self {
+                    // SAFETY: the caller promises the pointer is valid for reads and writes
+                    unsafe { Self::as_atomic(ptr) }.fetch_and(val, order)
+                }
+
+                #[inline(always)]
+                unsafe fn fetch_xor(ptr: NonNull<Self>, val: Self, order: Ordering) -> Self {
+                    // SAFETY: the caller promises the pointer is valid for reads and writes
+                    unsafe { Self::as_atomic(ptr) }.fetch_xor(val, order)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "use-atomics")]
+impl_atomic_integer!(
+    u8 => core::sync::atomic::AtomicU8, "8";
+    u16 => core::sync::atomic::AtomicU16, "16";
+    u32 => core::sync::atomic::AtomicU32, "32";
+    u64 => core::sync::atomic::AtomicU64, "64";
+    i8 => core::sync::atomic::AtomicI8, "8";
+    i16 => core::sync::atomic::AtomicI16, "16";
+    i32 => core::sync::atomic::AtomicI32, "32";
+    i64 => core::sync::atomic::AtomicI64, "64";
+);
+
+pub(crate) mod private {
+    use core::ptr::NonNull;
+    #[cfg(feature = "use-atomics")]
+    use core::sync::atomic::Ordering;
+
+    pub trait Sealed: Sized {
+        /// Perform a volatile read, or a relaxed atomic load with the `use-atomics` feature.
+        ///
+        /// # Safety
+        /// `ptr` must be [valid for reads](core::ptr::read_volatile#safety) and properly aligned.
+        unsafe fn load(ptr: NonNull<Self>) -> Self;
+
+        /// Perform a volatile write, or a relaxed atomic store with the `use-atomics` feature.
+        ///
+        /// # Safety
+        /// `ptr` must be [valid for writes](core::ptr::write_volatile#safety) and properly
+        /// aligned.
+        unsafe fn store(ptr: NonNull<Self>, val: Self);
+
+        /// Perform a read, apply `f`, and write the result if it is `Some`. The update is a
+        /// relaxed atomic operation with the `use-atomics` feature, where available, and two
+        /// separate volatile accesses otherwise.
+        ///
+        /// Returns `Ok` with the previous value if `f` returned `Some`, `Err` otherwise.
+        ///
+        /// # Safety
+        /// `ptr` must be [valid for reads](core::ptr::read_volatile#safety) and
+        /// [valid for writes](core::ptr::write_volatile#safety), and properly aligned.
+        unsafe fn fetch_update<F: FnMut(Self) -> Option<Self>>(
+            ptr: NonNull<Self>,
+            f: F,
+        ) -> Result<Self, Self>;
+    }
+
+    #[cfg(feature = "use-atomics")]
+    pub trait SealedAtomic: Sized {
+        type Atomic;
+
+        /// Returns a shared reference to the register as its atomic type.
+        ///
+        /// # Safety
+        /// `ptr` must be valid for the whole lifetime `'a`, and no non-atomic accesses to it can
+        /// happen concurrently.
+        ///
+        /// # Panics
+        /// If `ptr` is not aligned for the atomic type.
+        unsafe fn as_atomic<'a>(ptr: NonNull<Self>) -> &'a Self::Atomic;
+
+        /// Perform an atomic load.
+        ///
+        /// # Safety
+        /// `ptr` must be valid for reads, and no non-atomic accesses to it can happen
+        /// concurrently.
+        unsafe fn atomic_load(ptr: NonNull<Self>, order: Ordering) -> Self;
+
+        /// Perform an atomic store.
+        ///
+        /// # Safety
+        /// `ptr` must be valid for writes, and no non-atomic accesses to it can happen
+        /// concurrently.
+        unsafe fn atomic_store(ptr: NonNull<Self>, val: Self, order: Ordering);
+
+        /// Perform an atomic compare-and-exchange.
+        ///
+        /// # Safety
+        /// `ptr` must be valid for reads and writes, and no non-atomic accesses to it can happen
+        /// concurrently.
+        unsafe fn compare_exchange(
+            ptr: NonNull<Self>,
+            current: Self,
+            new: Self,
+            success: Ordering,
+            failure: Ordering,
+        ) -> Result<Self, Self>;
+
+        /// Perform an atomic bitwise OR, returning the previous value.
+        ///
+        /// # Safety
+        /// `ptr` must be valid for reads and writes, and no non-atomic accesses to it can happen
+        /// concurrently.
+        unsafe fn fetch_or(ptr: NonNull<Self>, val: Self, order: Ordering) -> Self;
+
+        /// Perform an atomic bitwise AND, returning the previous value.
+        ///
+        /// # Safety
+        /// `ptr` must be valid for reads and writes, and no non-atomic accesses to it can happen
+        /// concurrently.
+        unsafe fn fetch_and(ptr: NonNull<Self>, val: Self, order: Ordering) -> Self;
+
+        /// Perform an atomic bitwise XOR, returning the previous value.
+        ///
+        /// # Safety
+        /// `ptr` must be valid for reads and writes, and no non-atomic accesses to it can happen
+        /// concurrently.
+        unsafe fn fetch_xor(ptr: NonNull<Self>, val: Self, order: Ordering) -> Self;
+    }
 }
@@ -12,11 +12,12 @@ use crate::arr::ArrayElem;
 
 /// Iterator over a pointer array.
 ///
-/// This struct is created by the [`iter`](crate::reg::PtrArray::iter) and
-/// [`iter_slice`](crate::reg::PtrArray::iter_slice) methods on [`PtrArray`](crate::reg::PtrArray).
+/// This struct is created by the [`iter`](crate::RegArray::iter) and
+/// [`iter_slice`](crate::RegArray::iter_slice) methods on [`RegArray`](crate::RegArray).
 pub struct RegArrayIter<'a, P: ArrayElem<'a>> {
     start: NonNull<P::Target>,
     end: NonNull<P::Target>,
+    base: NonNull<[P::Target]>,
     _phantom: PhantomData<&'a ()>,
 }
 impl<'a, P: ArrayElem<'a>> Clone for RegArrayIter<'a, P> {
@@ -24,6 +25,7 @@ impl<'a, P: ArrayElem<'a>> Clone for RegArrayIter<'a, P> {
         RegArrayIter {
             start: Clone::clone(&self.start),
             end: Clone::clone(&self.end),
+            base: Clone::clone(&self.base),
             _phantom: Clone::clone(&self._phantom),
         }
     }
@@ -37,10 +39,21 @@ impl<'a, P: ArrayElem<'a>> RegArrayIter<'a, P> {
         Self {
             start,
             end,
+            base,
             _phantom: PhantomData,
         }
     }
 
+    /// Resets the iterator to the full range it was created with.
+    ///
+    /// After a reset, the iterator yields again all the pointers of the array (or of the subslice,
+    /// for iterators created with [`iter_slice`](crate::RegArray::iter_slice)), regardless of how
+    /// many were already consumed from either end.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new(self.base);
+    }
+
     /// Helper function for moving the start of the iterator forwards by `offset` elements,
     /// returning the old start.
     ///
@@ -147,12 +160,12 @@ impl<'a, P: ArrayElem<'a>> ExactSizeIterator for RegArrayIter<'a, P> {
         // - inside the allocation
         // - `start <= end`
 
-        // this is what core/slice/iter does, but it's unstable
+        // this is what core/slice/iter does, but `offset_from_unsigned` needs Rust 1.87
         // https://github.com/rust-lang/rust/issues/95892
-        // unsafe { self.end.sub_ptr(self.start) }
+        // unsafe { self.end.offset_from_unsigned(self.start) }
 
         // this is what the docs suggest as equivalent, but the codegen is less optimal
-        // https://doc.rust-lang.org/core/primitive.pointer.html#method.sub_ptr
+        // https://doc.rust-lang.org/core/primitive.pointer.html#method.offset_from_unsigned
         unsafe { usize::try_from(self.end.offset_from(self.start)).unwrap_unchecked() }
     }
 }
@@ -186,3 +199,47 @@ impl<'a, P: ArrayElem<'a>> DoubleEndedIterator for RegArrayIter<'a, P> {
 }
 
 impl<'a, P: ArrayElem<'a>> FusedIterator for RegArrayIter<'a, P> {}
+
+/// Minimal iterator over a pointer array, striding a raw pointer.
+///
+/// This struct is created by the [`iter_unchecked`](crate::RegArray::iter_unchecked) method on
+/// [`RegArray`](crate::RegArray).
+pub(crate) struct RegArrayStrideIter<'a, P: ArrayElem<'a>> {
+    ptr: NonNull<P::Target>,
+    remaining: usize,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a, P: ArrayElem<'a>> RegArrayStrideIter<'a, P> {
+    #[inline]
+    pub(crate) const fn new(ptr: NonNull<P::Target>, len: usize) -> Self {
+        Self {
+            ptr,
+            remaining: len,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, P: ArrayElem<'a>> Iterator for RegArrayStrideIter<'a, P> {
+    type Item = P;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let ptr = self.ptr;
+        // SAFETY: there was at least one element left, so we are at most one past the end
+        unsafe {
+            self.ptr = self.ptr.add(1);
+            Some(P::from_nonnull(ptr))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
@@ -8,11 +8,25 @@
 //! - [Register types](#register-types)
 //!   - [Basic registers](#basic-registers)
 //!   - [Nested register maps](#nested-register-maps)
+//!     - [Flattened register maps](#flattened-register-maps)
 //!   - [Arrays of registers](#arrays-of-registers)
 //!     - [Iterators](#iterators)
+//!     - [Runtime-length arrays](#runtime-length-arrays)
+//!   - [Custom register values](#custom-register-values)
+//!   - [Non-zero registers](#non-zero-registers)
+//!   - [Byte-array registers](#byte-array-registers)
 //! - [Access permissions](#access-permissions)
+//!   - [Nested register maps](#nested-register-maps-1)
 //! - [Type layout and representation](#type-layout-and-representation)
+//!   - [Field offsets](#field-offsets)
 //! - [Thread safety](#thread-safety)
+//!   - [The `use-atomics` feature](#the-use-atomics-feature)
+//!   - [The `rayon` feature](#the-rayon-feature)
+//! - [Mock registers](#mock-registers)
+//! - [Profiling](#profiling)
+//! - [Renamed dependency](#renamed-dependency)
+//! - [Extra derives on the pointer](#extra-derives-on-the-pointer)
+//! - [Runtime field information](#runtime-field-information)
 //! - [Principle of operation](#principle-of-operation)
 //!   - [Sample generated code](#sample-generated-code)
 //! - [Comparison with other crates](#comparison-with-other-crates)
@@ -50,7 +64,7 @@
 //!
 //! // when dealing with e.g. memory-mapped IO (MMIO),
 //! // you'd probably just get a pointer to the data from a known base address
-//! // let ptr = unsafe { RegistersPtr::from_ptr(0xAA55_000 as *mut _) };
+//! // let ptr = unsafe { RegistersPtr::from_static(0xAA55_000 as *mut _) };
 //!
 //! // all write() operations are volatile
 //! ptr.field1().write(10);
@@ -89,6 +103,29 @@
 //! ptr.write_only_field().read();   // error[E0277]: cannot read from a write-only register
 //! ```
 //!
+//! For register maps whose fields are all integers, booleans, floats, or arrays of them, the
+//! `#[reg_map(zeroed)]` attribute makes the derive macro also generate the associated constant
+//! `ZEROED` on the register map, with all registers set to zero. This is useful to create backing storage in `const` and `static` contexts, e.g. for a
+//! simulator on the host, where `Default` is not available:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap)]
+//! #[reg_map(zeroed)]
+//! # pub
+//! struct Timer {
+//!     count: u32,
+//!     compare: [u32; 4],
+//! }
+//! # } // mod yoo
+//! # use yoo::{Timer, TimerPtr};
+//! static mut BACKING: Timer = Timer::ZEROED;
+//!
+//! let ptr = unsafe { TimerPtr::from_static(core::ptr::addr_of_mut!(BACKING)) };
+//! assert_eq!(ptr.compare().idx(3).read(), 0);
+//! ```
+//!
 //! # Register types
 //!
 //! ## Basic registers
@@ -120,6 +157,9 @@
 //! where the read/write operations on the register are performed through the [`Reg`] type, and the
 //! access permissions default to both read and write.
 //!
+//! Doc comments on the register-map `struct` and on its fields are replicated on the derived
+//! pointer type and on its field-access methods, respectively.
+//!
 //! ## Nested register maps
 //! Register-map definitions can be nested arbitrarily:
 //! ```
@@ -148,6 +188,42 @@
 //! ```
 //! where `Basic` and `BasicPtr` are shown in the previous section.
 //!
+//! ### Flattened register maps
+//! Nested register maps are useful to group related registers, at the cost of more verbose
+//! accesses, e.g. `ptr.group().field()`. With the `#[reg(flatten)]` attribute, the accessors of
+//! the nested map are also forwarded on the outer pointer. The nested map itself must opt in with
+//! the `#[reg_map(flatten)]` attribute:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[derive(RegMap, Default)]
+//! #[reg_map(flatten)]
+//! #[repr(C)]
+//! # pub
+//! struct Group {
+//!     enable: u32,
+//!     data: [u32; 4],
+//! }
+//! #[derive(RegMap, Default)]
+//! #[repr(C)]
+//! # pub
+//! struct Flat {
+//!     id: u32,
+//!     #[reg(flatten)]
+//!     group: Group,
+//! }
+//! # } // mod yoo
+//! # use yoo::{Flat, FlatPtr};
+//! # let mut regs = Flat::default();
+//! # let ptr = FlatPtr::from_mut(&mut regs);
+//! ptr.enable().write(1); // same as `ptr.group().enable().write(1)`
+//! ptr.data().idx(2).write(3);
+//! ```
+//! The `#[reg_map(flatten)]` attribute generates a hidden `macro_rules!` macro defining the
+//! forwarding accessors, which is only visible within the crate. Therefore the flattened map must
+//! be defined in the same crate and in the same module as the outer map. Its accessors must not
+//! have the same name as other accessors of the outer map.
+//!
 //! ## Arrays of registers
 //! Fixed-size arrays of registers are also supported, with both basic and nested registers.
 //! ```
@@ -194,6 +270,15 @@
 //! # } // mod yoo
 //! ```
 //!
+//! The innermost elements of a multidimensional array can be accessed with a slice of indices
+//! through [`RegArray::at`], e.g. `ptr.basic().at(&[6, 4, 2, 1])`. For register maps made of a
+//! single array field, the `#[reg_map(at_path)]` attribute on the register map adds a method
+//! `at_path` to the derived pointer, that does the same on that field.
+//!
+//! The length of an array can be any constant expression, including one evaluating to zero. A
+//! zero-length array occupies no space in the register map, and its [`RegArray`] has no elements:
+//! iterators are empty, and [`RegArray::idx`] always panics.
+//!
 //! ### Iterators
 //!
 //! It is possible to iterate through arrays using the methods [`RegArray::iter`] and
@@ -237,6 +322,277 @@
 //! }
 //! ```
 //!
+//! The items yielded by the iterators keep the access permissions of the array. E.g. iterating
+//! over a read-only array yields read-only registers, and the following code does not compile:
+//! ```compile_fail,E0277
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! # #[derive(RegMap, Default)]
+//! # #[repr(C)]
+//! # pub struct ReadOnlyMany {
+//! #     #[reg(RO)]
+//! #     pub basic: [u64; 32],
+//! # }
+//! # } // mod yoo
+//! # use yoo::{ReadOnlyMany, ReadOnlyManyPtr};
+//! # let mut reg = ReadOnlyMany::default();
+//! # let ptr = ReadOnlyManyPtr::from_mut(&mut reg);
+//! for basic in ptr.basic().iter() {
+//!     basic.write(0); // error[E0277]: cannot write to a read-only register
+//! }
+//! ```
+//! The same holds for arrays of nested register maps:
+//! ```compile_fail,E0277
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! # #[derive(RegMap, Default)]
+//! # #[repr(C)]
+//! # pub struct Basic {
+//! #     pub field: u64,
+//! # }
+//! # #[derive(RegMap, Default)]
+//! # #[repr(C)]
+//! # pub struct ReadOnlyMany {
+//! #     #[reg(RO)]
+//! #     pub nested: [Basic; 16],
+//! # }
+//! # } // mod yoo
+//! # use yoo::{ReadOnlyMany, ReadOnlyManyPtr};
+//! # let mut reg = ReadOnlyMany::default();
+//! # let ptr = ReadOnlyManyPtr::from_mut(&mut reg);
+//! for nested in ptr.nested().iter_slice(2, 7) {
+//!     nested.field().write(0); // error[E0277]: cannot write to a read-only register
+//! }
+//! ```
+//! And to multidimensional arrays, where the access permission applies to every leaf register:
+//! ```compile_fail,E0277
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! # #[derive(RegMap, Default)]
+//! # #[repr(C)]
+//! # pub struct ReadOnlyGrid {
+//! #     #[reg(RO)]
+//! #     pub data: [[u32; 4]; 4],
+//! # }
+//! # } // mod yoo
+//! # use yoo::{ReadOnlyGrid, ReadOnlyGridPtr};
+//! # let mut reg = ReadOnlyGrid::default();
+//! # let ptr = ReadOnlyGridPtr::from_mut(&mut reg);
+//! for row in ptr.data().iter() {
+//!     row.idx(1).write(0); // error[E0277]: cannot write to a read-only register
+//! }
+//! ```
+//!
+//! ### Runtime-length arrays
+//!
+//! When the number of elements is only known at runtime, e.g. because it is read from a
+//! capability register of the device, a [`RegSlice`] can be created from a base pointer and a
+//! length with the `unsafe` function [`RegSlice::from_raw_parts`]:
+//! ```
+//! use reg_map::access::ReadWrite;
+//! use reg_map::{Reg, RegSlice};
+//!
+//! let mut buffer = vec![0u32; 16];
+//! let count = 12; // e.g. read from a capability register
+//! // SAFETY: the buffer has at least `count` elements, and outlives the slice
+//! let slice = unsafe { RegSlice::<Reg<u32, ReadWrite>>::from_raw_parts(buffer.as_mut_ptr(), count) };
+//!
+//! for (i, reg) in slice.iter().enumerate() {
+//!     reg.write(i as u32);
+//! }
+//! assert_eq!(slice.len(), 12);
+//! assert_eq!(slice.idx(11).read(), 11);
+//! assert!(slice.get(12).is_none());
+//! ```
+//! A `RegArray` can also be viewed as a `RegSlice` with [`RegArray::as_slice`].
+//!
+//! C structs ending with a flexible array member, e.g. `uint32_t data[];`, are modeled with a
+//! trailing zero-length array field `data: [u32; 0]`. With the `#[reg_map(tail)]` attribute on
+//! the register map, the derive macro then generates the `unsafe` method `tail(len)`, returning a
+//! `RegSlice` of `len` elements starting at the trailing field. The length of the trailing array
+//! can be any constant expression, and is checked to be zero at compile time:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap)]
+//! #[reg_map(tail)]
+//! # pub
+//! struct Message {
+//!     len: u32,
+//!     data: [u32; 0],
+//! }
+//! # } // mod yoo
+//! # use yoo::MessagePtr;
+//! let mut buffer = [3u32, 0, 0, 0];
+//! let ptr = unsafe { MessagePtr::from_ptr(buffer.as_mut_ptr().cast()) };
+//! let len = ptr.len().read() as usize;
+//! // SAFETY: the buffer holds `len` elements after the header
+//! let data = unsafe { ptr.tail(len) };
+//! data.idx(2).write(42);
+//! assert_eq!(buffer, [3, 0, 0, 42]);
+//! ```
+//!
+//! ## Custom register values
+//! Registers storing an integer can be read and written as a custom type, by implementing the
+//! trait [`RegValue`](integers::RegValue) for the custom type and using the `#[reg(via = Type)]`
+//! attribute on the integer field:
+//! ```
+//! # mod yoo {
+//! use reg_map::integers::RegValue;
+//! # use reg_map::RegMap;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! pub struct Ipv4(pub [u8; 4]);
+//!
+//! impl RegValue for Ipv4 {
+//!     type Bits = u32;
+//!     fn from_bits(bits: u32) -> Self {
+//!         Self(bits.to_be_bytes())
+//!     }
+//!     fn to_bits(self) -> u32 {
+//!         u32::from_be_bytes(self.0)
+//!     }
+//! }
+//!
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! # pub
+//! struct Network {
+//!     #[reg(via = Ipv4)]
+//!     address: u32,
+//!     #[reg(RO, via = Ipv4)]
+//!     gateways: [u32; 4],
+//! }
+//! # } // mod yoo
+//! # use yoo::{Ipv4, Network, NetworkPtr};
+//! let mut regs = Network::default();
+//! let ptr = NetworkPtr::from_mut(&mut regs);
+//!
+//! ptr.address().write(Ipv4([192, 168, 0, 1]));
+//! assert_eq!(ptr.address().read(), Ipv4([192, 168, 0, 1]));
+//! assert_eq!(ptr.gateways().idx(0).read(), Ipv4([0, 0, 0, 0]));
+//! ```
+//! The derive macro then generates a `Reg<'a, Ipv4, ReadWrite>` for the field `address`, and a
+//! `RegArray<'a, Reg<'a, Ipv4, ReadOnly>, 4>` for the field `gateways`. The type of the field in
+//! the register map must match the associated type [`RegValue::Bits`](integers::RegValue::Bits).
+//!
+//! ## Non-zero registers
+//! Registers documented as never being zero can be declared with the corresponding non-zero
+//! integer type, e.g. [`NonZeroU32`](core::num::NonZeroU32). The derive macro then generates a
+//! [`RegNonZero<'a, u32, A>`](RegNonZero): reads return `None` if the hardware unexpectedly
+//! returns zero, and only non-zero values can be written, so the underlying struct never holds
+//! an invalid value:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! use core::num::NonZeroU32;
+//!
+//! #[repr(C)]
+//! #[derive(RegMap)]
+//! # pub
+//! struct Timer {
+//!     pub divider: NonZeroU32,
+//!     #[reg(RO)]
+//!     pub prescalers: [NonZeroU32; 2],
+//! }
+//! # } // mod yoo
+//! # use yoo::{Timer, TimerPtr};
+//! # use core::num::NonZeroU32;
+//! let one = NonZeroU32::new(1).unwrap();
+//! let mut regs = Timer {
+//!     divider: one,
+//!     prescalers: [one; 2],
+//! };
+//! let ptr = TimerPtr::from_mut(&mut regs);
+//!
+//! ptr.divider().write(NonZeroU32::new(8).unwrap());
+//! assert_eq!(ptr.divider().read(), NonZeroU32::new(8));
+//! assert_eq!(ptr.prescalers().idx(1).read(), Some(one));
+//! ```
+//!
+//! ## Boolean registers
+//! Single-byte enable flags can be declared as [`bool`] fields. The derive macro then generates a
+//! `Reg<'a, bool, A>`, whose reads and writes access the underlying byte as a [`u8`]:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! # pub
+//! struct Dma {
+//!     pub enable: bool,
+//!     #[reg(RO)]
+//!     pub busy: [bool; 4],
+//! }
+//! # } // mod yoo
+//! # use yoo::{Dma, DmaPtr};
+//! let mut regs = Dma::default();
+//! let ptr = DmaPtr::from_mut(&mut regs);
+//!
+//! ptr.enable().write(true);
+//! assert!(ptr.enable().read());
+//! assert!(!ptr.busy().idx(2).read());
+//! ```
+//! Writes store `0` or `1`, and any non-zero byte read from the hardware is converted to `true`,
+//! so reads are sound even if the register holds a value other than `0` or `1`. However, if the
+//! register map is backed by ordinary memory, e.g. through `from_mut`, the `bool` fields of the
+//! underlying struct must only hold `0` or `1` when accessed directly.
+//!
+//! ## Floating-point registers
+//! Registers holding IEEE-754 values, e.g. the coefficients of a DSP accelerator, can be declared
+//! as [`f32`] or [`f64`] fields. The derive macro then generates a `Reg<'a, f32, A>` or
+//! `Reg<'a, f64, A>`, whose reads and writes access the bits of the value as a [`u32`] or [`u64`]:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! # pub
+//! struct Filter {
+//!     pub gain: f32,
+//!     pub taps: [f64; 8],
+//! }
+//! # } // mod yoo
+//! # use yoo::{Filter, FilterPtr};
+//! let mut regs = Filter::default();
+//! let ptr = FilterPtr::from_mut(&mut regs);
+//!
+//! ptr.gain().write(0.5);
+//! ptr.taps().idx(3).write(-1.25);
+//! assert_eq!(ptr.gain().read(), 0.5);
+//! assert_eq!(ptr.taps().idx(3).read(), -1.25);
+//! ```
+//! The bits are converted with [`f32::from_bits`] and [`f32::to_bits`], so any bit pattern is a
+//! valid value. Note that reads may observe NaNs, including signaling NaNs, if the hardware
+//! produces them: compare with [`f32::is_nan`] rather than `==` where this matters.
+//!
+//! ## Byte-array registers
+//! Some registers are documented as a fixed-size byte array that is accessed as a single unit,
+//! e.g. a 16-byte key register. A field of type `[u8; N]` annotated with `#[reg(bytes)]` generates
+//! a [`RegBytes`] instead of an array of one-byte registers, and each read or write is a single
+//! volatile access of the whole array:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! # pub
+//! struct Crypto {
+//!     #[reg(WO, bytes)]
+//!     key: [u8; 16],
+//!     #[reg(RO, bytes)]
+//!     digest: [u8; 8],
+//! }
+//! # } // mod yoo
+//! # use yoo::{Crypto, CryptoPtr};
+//! let mut regs = Crypto::default();
+//! let ptr = CryptoPtr::from_mut(&mut regs);
+//!
+//! ptr.key().write(*b"0123456789abcdef");
+//! assert_eq!(ptr.digest().read(), [0; 8]);
+//! ```
+//!
 //! # Access permissions
 //! Access permissions for each register can be specified with the `#[reg()]` attribute, and
 //! default to read-write if not specified:
@@ -249,20 +605,187 @@
 //!     #[reg(RO)] read_only_register: u64,
 //!     #[reg(WO)] write_only_register: u64,
 //!     #[reg(RW)] read_write_register: u64,
-//!     another_read_only_register: u64,
+//!     #[reg(RC)] read_to_clear_register: u64,
+//!     #[reg(W1C)] write_one_to_clear_register: u64,
+//!     another_read_write_register: u64,
 //! }
 //! # } // mod yoo
 //! ```
 //! Access permission are implemented through the zero-sized structs:
 //! - [`ReadOnly`](access::ReadOnly) for read-only registers (`#[reg(RO)]` attribute);
 //! - [`WriteOnly`](access::WriteOnly) for write-only registers (`#[reg(WO)]` attribute);
-//! - [`ReadWrite`](access::ReadWrite) for read-write registers (`#[reg(RW)]` attribute, or no attribute).
+//! - [`ReadWrite`](access::ReadWrite) for read-write registers (`#[reg(RW)]` attribute, or no attribute);
+//! - [`ReadClear`](access::ReadClear) for read-only registers that are cleared by reading them,
+//!   e.g. status registers with read-to-clear flags (`#[reg(RC)]` attribute);
+//! - [`Write1Clear`](access::Write1Clear) for registers whose flags are cleared by writing `1`
+//!   to them, e.g. interrupt-flag registers (`#[reg(W1C)]` attribute).
 //!
 //! Access permission are checked at compile time, as the zero-sized structs above are passed as
 //! type parameters to the generic types [`Reg`] and [`RegArray`] upon definition of the derived
 //! pointer types. Specifically, the [`write`](Reg::write) is just not defined for a read-only
 //! register, and so on.
 //!
+//! Reading a read-to-clear register is the same volatile read as for a read-only register. The
+//! distinction is only at the type level, to document the side effect of the read. Like read-only
+//! registers, read-to-clear registers cannot be written, nor modified with e.g. [`Reg::modify`]
+//! or [`Reg::set_bit`]. The following code does not compile:
+//! ```compile_fail,E0277
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! # pub
+//! struct Uart {
+//!     #[reg(RC)]
+//!     status: u32,
+//! }
+//! # } // mod yoo
+//! # use yoo::{Uart, UartPtr};
+//! # let mut regs = Uart::default();
+//! # let ptr = UartPtr::from_mut(&mut regs);
+//! ptr.status().write(0); // error[E0277]: cannot write to a read-only register
+//! ```
+//!
+//! Write-1-to-clear registers can be read and written, but flags are cleared with a single write
+//! of the mask of the flags to clear, using [`Reg::clear_flags`]. Read-modify-write operations
+//! would clear all the flags that are set, and are only available on read-write registers,
+//! implementing [`Modifiable`](access::Modifiable). The following code does not compile:
+//! ```compile_fail,E0277
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! # pub
+//! struct Interrupts {
+//!     #[reg(W1C)]
+//!     pending: u32,
+//! }
+//! # } // mod yoo
+//! # use yoo::{Interrupts, InterruptsPtr};
+//! # let mut regs = Interrupts::default();
+//! # let ptr = InterruptsPtr::from_mut(&mut regs);
+//! ptr.pending().set_bit(2); // error[E0277]: cannot read-modify-write a `Write1Clear` register
+//! ```
+//!
+//! ## Nested register maps
+//! The `#[reg()]` attribute can also be placed on a nested register map, or on an array of nested
+//! register maps. The access permission is then propagated to every register of the nested map,
+//! restricting the permissions defined in the nested map itself:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! # pub
+//! struct Status {
+//!     flags: u32,
+//!     #[reg(RO)] count: u32,
+//! }
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! # pub
+//! struct Device {
+//!     #[reg(RO)] items: [Status; 16],
+//!     control: Status,
+//! }
+//! # } // mod yoo
+//! # use yoo::{Device, DevicePtr};
+//! # let mut regs = Device::default();
+//! # let ptr = DevicePtr::from_mut(&mut regs);
+//! ptr.items().idx(3).flags().read(); // read only
+//! ptr.control().flags().write(1);    // read write
+//! ```
+//!
+//! The pointers to the nested maps carry the access permission as a second generic parameter,
+//! e.g. `StatusPtr<'a, ReadOnly>` for the elements of `items`. The permission defaults to
+//! [`ReadWrite`](access::ReadWrite), so that `StatusPtr<'a>` is the same as
+//! `StatusPtr<'a, ReadWrite>`. The following code does not compile:
+//! ```compile_fail,E0277
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! # #[repr(C)]
+//! # #[derive(RegMap, Default)]
+//! # pub struct Status {
+//! #     flags: u32,
+//! #     #[reg(RO)] count: u32,
+//! # }
+//! # #[repr(C)]
+//! # #[derive(RegMap, Default)]
+//! # pub struct Device {
+//! #     #[reg(RO)] items: [Status; 16],
+//! #     control: Status,
+//! # }
+//! # } // mod yoo
+//! # use yoo::{Device, DevicePtr};
+//! # let mut regs = Device::default();
+//! # let ptr = DevicePtr::from_mut(&mut regs);
+//! ptr.items().idx(3).flags().write(1); // error[E0277]: cannot write to a read-only register
+//! ```
+//!
+//! The permission propagates through any number of array dimensions and levels of nesting, down
+//! to every leaf register. E.g. all the registers of all the `Status` maps in a
+//! `#[reg(RO)] banks: [[Status; 4]; 2]` field are read-only:
+//! ```compile_fail,E0277
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! # #[repr(C)]
+//! # #[derive(RegMap, Default)]
+//! # pub struct Status {
+//! #     flags: u32,
+//! #     #[reg(RO)] count: u32,
+//! # }
+//! # #[repr(C)]
+//! # #[derive(RegMap, Default)]
+//! # pub struct Device {
+//! #     #[reg(RO)] banks: [[Status; 4]; 2],
+//! # }
+//! # } // mod yoo
+//! # use yoo::{Device, DevicePtr};
+//! # let mut regs = Device::default();
+//! # let ptr = DevicePtr::from_mut(&mut regs);
+//! for bank in ptr.banks().iter() {
+//!     bank.idx(0).flags().write(1); // error[E0277]: cannot write to a read-only register
+//! }
+//! ```
+//!
+//! A read-only permission and a write-only permission cannot be combined: accessing a `#[reg(RO)]`
+//! register inside of a `#[reg(WO)]` nested map is a compile-time error. See
+//! [`Restrict`](access::Restrict) for the details.
+//!
+//! ## Clearing sensitive registers
+//!
+//! With the `#[reg_map(secure_clear)]` attribute on the register map, the derived pointer has a
+//! `secure_clear` method that writes zero to all the writable registers of the map, including
+//! those of nested maps, and then issues a
+//! [`compiler_fence`](core::sync::atomic::compiler_fence) so that the writes are not elided.
+//! Read-only and read-to-clear registers are left untouched, and so are registers declared with a
+//! non-zero integer type, since zero is not a valid value for them. This is useful to scrub key
+//! material from a crypto peripheral after use:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! #[reg_map(secure_clear)]
+//! # pub
+//! struct Aes {
+//!     #[reg(RO)]
+//!     status: u32,
+//!     key: [u32; 8],
+//! }
+//! # } // mod yoo
+//! # use yoo::{Aes, AesPtr};
+//! # let mut regs = Aes::default();
+//! # let ptr = AesPtr::from_mut(&mut regs);
+//! ptr.key().idx(0).write(0x2b7e_1516);
+//! ptr.secure_clear();
+//! assert_eq!(ptr.key().idx(0).read(), 0);
+//! ```
+//!
+//! Nested register maps must have the `#[reg_map(secure_clear)]` attribute too. The method is not
+//! available on pointers whose access permission is not writable, e.g. on a `#[reg(RO)]` nested
+//! map.
+//!
 //! # Type layout and representation
 //! The derive macro [`RegMap`] requires the register-map `struct` to have the `C` representation
 //! using the `#[repr(C)]` attribute. Higher alignment requirements can be specified with the
@@ -293,10 +816,51 @@
 //! - Default/`Rust` representation is *not* supported.
 //! - `#[repr(transparent)]`: The `transparent` representation is *not* supported.
 //! - `#[repr(align(x))]`: *Raising* the alignment of the register map is supported, in combination
-//!   with the `C` representation.
+//!   with the `C` representation. The derive macro forwards `x` as is, but the compiler currently
+//!   requires it to be an integer literal.
 //! - `#[repr(packed)]`: *Lowering* the alignment of the register map is *not* supported.
 //!   This is because unaligned reads and writes are not (currently) supported.
 //!
+//! For example, a constant alignment is rejected by the compiler, not by the derive macro:
+//! ```compile_fail,E0693
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! const PAGE_SIZE: usize = 4096;
+//! #[repr(C, align(PAGE_SIZE))] // error[E0693]: `align` expects a literal integer as argument
+//! #[derive(RegMap)]
+//! struct Page {
+//!     data: [u64; 512],
+//! }
+//! # } // mod yoo
+//! ```
+//!
+//! ## Field offsets
+//!
+//! The byte offset of each field, including padding, is available as an associated constant of
+//! the derived pointer type, named after the field in uppercase, e.g. to cross-check the register
+//! map against a datasheet:
+//! ```
+//! # mod yoo {
+//! # use reg_map::RegMap;
+//! #[repr(C)]
+//! #[derive(RegMap)]
+//! # pub
+//! struct Device {
+//!     id: u8,
+//!     control: u32,
+//!     fifo: [u64; 4],
+//! }
+//! # } // mod yoo
+//! # use yoo::DevicePtr;
+//! assert_eq!(DevicePtr::ID_OFFSET, 0x00);
+//! assert_eq!(DevicePtr::CONTROL_OFFSET, 0x04);
+//! assert_eq!(DevicePtr::FIFO_OFFSET, 0x08);
+//! ```
+//!
+//! Offsets are generated for nested register maps too, so that a pointer to a nested map can be
+//! rebuilt from the base address of the outer map, e.g. in a module that only knows the base
+//! address: `InnerPtr::from_ptr(base.byte_add(OuterPtr::INNER_OFFSET).cast())`.
+//!
 //! # Thread safety
 //!
 //! All reads and writes performed through the pointers derived by [`RegMap`] are volatile. However
@@ -334,6 +898,119 @@
 //! If something goes wrong, that's on you! See also
 //! [URLO: Volatile + relaxed atomic load/store](https://users.rust-lang.org/t/volatile-relaxed-atomic-load-store/92792).
 //!
+//! ## The `use-atomics` feature
+//!
+//! With the `use-atomics` feature enabled, [`Reg::read`] and [`Reg::write`] are performed as
+//! relaxed atomic loads and stores instead of volatile reads and writes, for all register widths
+//! with a matching atomic type on the target (e.g. [`AtomicU32`](core::sync::atomic::AtomicU32)
+//! for [`u32`]). Registers of type [`u128`] and [`i128`], and registers whose atomic type has a
+//! stricter alignment than the integer type, keep using volatile accesses.
+//!
+//! Note that atomic accesses are performed through short-lived shared references to the atomic
+//! types, and that the feature does *not* implement [`Send`] or [`Sync`] on the derived pointers.
+//!
+//! The feature also enables atomic read-modify-write operations on registers whose type implements
+//! `AtomicInteger`, e.g. `Reg::compare_exchange` and the bitwise `Reg::fetch_or`, `Reg::fetch_and`
+//! and `Reg::fetch_xor` for lock-free status registers, and atomic accesses with explicit ordering for
+//! producer/consumer handshakes, `Reg::load_acquire` and `Reg::store_release`.
+//!
+//! ## The `rayon` feature
+//!
+//! For host-side bulk processing of large register arrays, e.g. in a simulator, the `rayon`
+//! feature adds `RegArray::par_iter`, which splits the elements of an array across the threads of
+//! the [rayon](https://docs.rs/rayon) thread pool. The method is `unsafe`: the caller asserts that
+//! the registers can be accessed concurrently from multiple threads.
+//!
+//! ## The `fixed` feature
+//!
+//! For DSP and control code storing Q-format values in integer registers, the `fixed` feature adds
+//! `Reg::read_fixed` and `Reg::write_fixed`, which reinterpret the bits of a register as a
+//! fixed-point number of the [fixed](https://docs.rs/fixed) crate with the same underlying
+//! integer, e.g. `FixedU32<U16>` for a [`u32`] register.
+//!
+//! # Mock registers
+//!
+//! To unit-test driver logic on the host, driver code can be written generic over the trait
+//! [`RegisterAccess`], implemented by read-write [`Reg`]s. With the `std` feature, the `mock`
+//! module provides `MockReg`, a register backed by a [`Cell`](core::cell::Cell) that also
+//! implements [`RegisterAccess`] and can be used in place of a real register in tests.
+//!
+//! # Profiling
+//!
+//! With the `profile` feature, which enables the `std` feature, the number of reads and writes of
+//! each register is counted in a global table keyed by the address of the register, and can be
+//! queried with `profile::counts`. This helps finding the hot registers of a driver, e.g. when
+//! running it against a simulator on the host.
+//!
+//! # Renamed dependency
+//!
+//! The code generated by the derive macro [`RegMap`] refers to this crate as `::reg_map`. If the
+//! crate is renamed in `Cargo.toml`, or re-exported by another crate, the path to use in the
+//! generated code can be set with the `#[reg_map(crate = "path")]` attribute on the register map:
+//! ```
+//! # extern crate reg_map as my_reg_map;
+//! # mod yoo {
+//! use my_reg_map::RegMap;
+//!
+//! #[repr(C)]
+//! #[derive(RegMap)]
+//! #[reg_map(crate = "my_reg_map")]
+//! struct Renamed {
+//!     data: u64,
+//! }
+//! # } // mod yoo
+//! ```
+//!
+//! # Extra derives on the pointer
+//!
+//! The pointer type generated by the derive macro [`RegMap`] does not implement any trait by
+//! default. Additional derive macros can be applied to it with the
+//! `#[reg_map(ptr_derive(...))]` attribute on the register map, which is forwarded as a
+//! `#[derive(...)]` attribute on the pointer type:
+//! ```
+//! # mod yoo {
+//! use reg_map::RegMap;
+//!
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! #[reg_map(ptr_derive(Debug, Clone, Copy))]
+//! pub struct Registers {
+//!     data: u64,
+//! }
+//! # } // mod yoo
+//! # use yoo::{Registers, RegistersPtr};
+//! let mut regs = Registers::default();
+//! let ptr = RegistersPtr::from_mut(&mut regs);
+//! let copy = ptr;
+//! copy.data().write(42);
+//! assert_eq!(ptr.data().read(), 42);
+//! ```
+//!
+//! # Runtime field information
+//!
+//! With the `#[reg_map(fields)]` attribute on the register map, the derived pointer has a method
+//! `fields()` returning an iterator over the [`FieldInfo`] of the registers, e.g. to implement a
+//! register browser:
+//! ```
+//! # mod yoo {
+//! use reg_map::RegMap;
+//!
+//! #[repr(C)]
+//! #[derive(RegMap, Default)]
+//! #[reg_map(fields)]
+//! pub struct Registers {
+//!     #[reg(RO)]
+//!     id: u16,
+//!     enable: bool,
+//! }
+//! # } // mod yoo
+//! # use yoo::{Registers, RegistersPtr};
+//! let mut regs = Registers::default();
+//! let ptr = RegistersPtr::from_mut(&mut regs);
+//! let names: Vec<_> = ptr.fields().map(|f| f.name).collect();
+//! assert_eq!(names, ["id", "enable"]);
+//! ```
+//!
 //! # Principle of operation
 //!
 //! The derive macro [`RegMap`] takes as input the definition of a register map (a `struct`), and
@@ -384,20 +1061,14 @@
 //! mod _mod_Test {
 //!     use super::*;
 //!
-//!     pub(super) struct TestPtr<'a> {
+//!     pub(super) struct TestPtr<'a, A: ::reg_map::access::Access = ::reg_map::access::ReadWrite> {
 //!         ptr: ::core::ptr::NonNull<Test>,
 //!         _ref: ::core::marker::PhantomData<&'a Test>,
+//!         _acs: ::core::marker::PhantomData<A>,
 //!     }
 //!
 //!     impl<'a> TestPtr<'a> {
 //!         #[inline]
-//!         const unsafe fn from_nonnull(ptr: ::core::ptr::NonNull<Test>) -> Self {
-//!             Self {
-//!                 ptr,
-//!                 _ref: ::core::marker::PhantomData,
-//!             }
-//!         }
-//!         #[inline]
 //!         pub const unsafe fn from_ptr(ptr: *mut Test) -> Self {
 //!             Self::from_nonnull(::core::ptr::NonNull::new_unchecked(ptr))
 //!         }
@@ -406,11 +1077,35 @@
 //!             unsafe { Self::from_ptr(reg) }
 //!         }
 //!         #[inline]
+//!         pub const fn from_unsafe_cell(cell: &'a ::core::cell::UnsafeCell<Test>) -> Self {
+//!             unsafe { Self::from_ptr(cell.get()) }
+//!         }
+//!         pub const SCALAR_FIELD_OFFSET: usize = ::core::mem::offset_of!(Test, scalar_field);
+//!         pub const ARRAY_FIELD_OFFSET: usize = ::core::mem::offset_of!(Test, array_field);
+//!     }
+//!
+//!     impl TestPtr<'static> {
+//!         #[inline]
+//!         pub const unsafe fn from_static(ptr: *mut Test) -> Self {
+//!             Self::from_ptr(ptr)
+//!         }
+//!     }
+//!
+//!     impl<'a, A: ::reg_map::access::Access> TestPtr<'a, A> {
+//!         #[inline]
+//!         const unsafe fn from_nonnull(ptr: ::core::ptr::NonNull<Test>) -> Self {
+//!             Self {
+//!                 ptr,
+//!                 _ref: ::core::marker::PhantomData,
+//!                 _acs: ::core::marker::PhantomData,
+//!             }
+//!         }
+//!         #[inline]
 //!         pub const fn as_ptr(&self) -> *mut Test {
 //!             self.ptr.as_ptr()
 //!         }
 //!         #[inline]
-//!         pub fn scalar_field(&self) -> ::reg_map::Reg<'a, u64, ::reg_map::access::ReadWrite> {
+//!         pub fn scalar_field(&self) -> ::reg_map::Reg<'a, u64, A> {
 //!             unsafe {
 //!                 ::reg_map::Reg::__MACRO_ONLY__from_ptr(::core::ptr::addr_of_mut!(
 //!                     (*self.as_ptr()).scalar_field
@@ -418,10 +1113,7 @@
 //!             }
 //!         }
 //!         #[inline]
-//!         pub fn array_field(
-//!             &self,
-//!         ) -> ::reg_map::RegArray<'a, ::reg_map::Reg<'a, u64, ::reg_map::access::ReadWrite>, 4096>
-//!         {
+//!         pub fn array_field(&self) -> ::reg_map::RegArray<'a, ::reg_map::Reg<'a, u64, A>, 4096> {
 //!             unsafe {
 //!                 ::reg_map::RegArray::__MACRO_ONLY__from_ptr(::core::ptr::addr_of_mut!(
 //!                     (*self.as_ptr()).array_field
@@ -430,19 +1122,21 @@
 //!         }
 //!     }
 //!
-//!     unsafe impl<'a> ::reg_map::RegMapPtr<'a> for TestPtr<'a> {
+//!     unsafe impl<'a, A: ::reg_map::access::Access> ::reg_map::RegMapPtr<'a> for TestPtr<'a, A> {
 //!         type RegMap = Test;
 //!         #[inline]
 //!         unsafe fn from_nonnull(ptr: ::core::ptr::NonNull<Self::RegMap>) -> Self {
 //!             Self::from_nonnull(ptr)
 //!         }
 //!         #[inline]
+//!         #[track_caller]
 //!         unsafe fn from_ptr(ptr: *mut Self::RegMap) -> Self {
-//!             Self::from_ptr(ptr)
+//!             ::core::debug_assert!(ptr.is_aligned(), "misaligned register map pointer");
+//!             Self::from_nonnull(::core::ptr::NonNull::new_unchecked(ptr))
 //!         }
 //!         #[inline]
 //!         fn from_mut(reg: &'a mut Self::RegMap) -> Self {
-//!             Self::from_mut(reg)
+//!             unsafe { Self::from_nonnull(::core::ptr::NonNull::from(reg)) }
 //!         }
 //!         #[inline]
 //!         fn as_ptr(&self) -> *mut Self::RegMap {
@@ -461,11 +1155,18 @@
 //! The defined `TestPtr` type is then re-exported out of the module.
 //!
 //! `TestPtr` itself is just a wrapper around a [`NonNull`](core::ptr::NonNull) pointer, plus a
-//! marker field to signal that it is semantically a `&'a Test`.
+//! marker field to signal that it is semantically a `&'a Test`, and a marker field for the access
+//! permission `A` of the whole register map. The permission `A` is [`ReadWrite`](access::ReadWrite)
+//! unless the map is nested in another map with a `#[reg()]` attribute, see
+//! [Nested register maps](#nested-register-maps-1) in the access permissions.
 //!
-//! A new `TestPtr` can be safely constructed from a `&mut Test` through `TestPtr::from_mut`, or
-//! `unsafe`ly from a `*mut Test` through `TestPtr::from_ptr`. A raw pointer to the underlying data
-//! can be obtained from a live `TestPtr` with the method `TestPtr::as_ptr`.
+//! A new `TestPtr` can be safely constructed from a `&mut Test` through `TestPtr::from_mut` or
+//! from a `&UnsafeCell<Test>` through `TestPtr::from_unsafe_cell`, or `unsafe`ly from a
+//! `*mut Test` through `TestPtr::from_ptr`, or `TestPtr::from_static` for a `TestPtr<'static>`,
+//! e.g. to a `static mut` or to a peripheral at a fixed address. A raw pointer to the underlying data
+//! can be obtained from a live `TestPtr` with the method `TestPtr::as_ptr`. The byte offsets of
+//! the fields are the associated constants `TestPtr::SCALAR_FIELD_OFFSET` and
+//! `TestPtr::ARRAY_FIELD_OFFSET`.
 //!
 //! The juice of the generated code are the `TestPtr::scalar_field` and `TestPtr::array_field`
 //! methods, which use [`addr_of_mut!`](core::ptr::addr_of_mut) to return a [`Reg`] and a
@@ -475,6 +1176,11 @@
 //! Finally, the generated code implements the [`RegMapPtr`] trait on `TestPtr` so that it can be
 //! stored in a [`RegArray`], if needed.
 //!
+//! Not shown above, the optional methods `fields`, `at_path`, `tail` and `secure_clear`, the
+//! constant `ZEROED` on the register map, and the hidden `macro_rules!` macro that defines the
+//! forwarding accessors when `Test` is nested in another map with `#[reg(flatten)]`, are only
+//! generated with the corresponding `#[reg_map(...)]` attribute on the register map.
+//!
 //! # Comparison with other crates
 //!
 //! ## `volatile`
@@ -502,6 +1208,9 @@
 //! For the cases where the `volatile-register` approach happens to work, the assembly generated by
 //! the two approaches is identical.
 //!
+//! To ease migration from `volatile-register`, the `compat` feature of this crate adds the methods
+//! `get` and `set` to [`Reg`], as aliases of [`read`](Reg::read) and [`write`](Reg::write).
+//!
 //! # Further reading
 //!
 //! Some links to relevant forum threads and GitHub issues:
@@ -514,6 +1223,9 @@
 
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 /// Derive macro to generate a pointer to a register map with volatile reads and writes.
 ///
 /// See the [top-level documentation](crate) for usage information and examples.
@@ -522,13 +1234,34 @@ pub use reg_map_derive::RegMap;
 pub mod access;
 
 mod arr;
-pub use arr::{ArrayElem, RegArray};
+pub use arr::{ArrayElem, ArrayTypes, RegArray};
 
 mod bounds;
 
+mod field;
+pub use field::FieldInfo;
+
 pub mod integers;
 
+#[cfg(feature = "std")]
+pub mod mock;
+
 mod iter;
+pub use iter::RegArrayIter;
+
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "rayon")]
+pub use par::RegArrayParIter;
+
+#[cfg(feature = "profile")]
+pub mod profile;
 
 mod reg;
-pub use reg::{Reg, RegMapPtr};
+pub use reg::{DefaultVolatile, Reg, RegBytes, RegMapPtr, RegNonZero, RegisterAccess, VolatileOp};
+
+mod shadow;
+pub use shadow::ShadowArray;
+
+mod slice;
+pub use slice::RegSlice;
@@ -0,0 +1,62 @@
+//! Host-side mock registers, to unit-test driver logic off-target.
+//!
+//! Requires the `std` feature.
+
+use core::cell::Cell;
+
+use crate::integers::RegValue;
+use crate::reg::RegisterAccess;
+
+#[cfg(doc)]
+use crate::Reg;
+
+/// A mock register backed by a [`Cell`], with the same `read`/`write`/`modify` surface as a
+/// read-write [`Reg`].
+///
+/// Both `MockReg` and [`Reg`] implement [`RegisterAccess`], so that driver code generic over
+/// `RegisterAccess` can be tested on the host against mock registers.
+#[derive(Debug, Default, Clone)]
+pub struct MockReg<T: RegValue> {
+    val: Cell<T>,
+}
+
+impl<T: RegValue> MockReg<T> {
+    /// Creates a new mock register with initial value `val`.
+    #[inline]
+    pub const fn new(val: T) -> Self {
+        Self {
+            val: Cell::new(val),
+        }
+    }
+    /// Read the value of the mock register.
+    #[inline]
+    pub fn read(&self) -> T {
+        self.val.get()
+    }
+    /// Write the value of the mock register.
+    #[inline]
+    pub fn write(&self, val: T) {
+        self.val.set(val)
+    }
+    /// Read the value of the mock register, apply `f` to it, and write the result.
+    #[inline]
+    pub fn modify<F: FnOnce(T) -> T>(&self, f: F) {
+        self.write(f(self.read()))
+    }
+    /// Consumes the mock register, returning its value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.val.into_inner()
+    }
+}
+
+impl<T: RegValue> RegisterAccess<T> for MockReg<T> {
+    #[inline]
+    fn read(&self) -> T {
+        MockReg::read(self)
+    }
+    #[inline]
+    fn write(&self, val: T) {
+        MockReg::write(self, val)
+    }
+}
@@ -0,0 +1,92 @@
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+use crate::arr::{ArrayElem, RegArray};
+
+/// A parallel iterator over a [`RegArray`], returned by [`RegArray::par_iter`].
+///
+/// The register pointers are not [`Send`], so they cannot be the items of a parallel iterator.
+/// Instead, the iterator splits the index range across threads, and each task constructs the
+/// pointer to its element from the base address of the array and passes it to a closure, e.g.
+/// with [`RegArrayParIter::map`] or [`RegArrayParIter::for_each`].
+///
+/// Requires the `rayon` feature.
+pub struct RegArrayParIter<'a, P: ArrayElem<'a>, const N: usize> {
+    base: SendPtr<P::Target>,
+    _ref: PhantomData<fn() -> P>,
+}
+
+impl<'a, P: ArrayElem<'a>, const N: usize> RegArrayParIter<'a, P, N> {
+    /// # Safety
+    /// See [`RegArray::par_iter`].
+    #[inline]
+    pub(crate) unsafe fn new(arr: &RegArray<'a, P, N>) -> Self {
+        // SAFETY: the pointer of a `RegArray` is never null
+        let base = unsafe { NonNull::new_unchecked(arr.as_ptr().cast::<P::Target>()) };
+        Self {
+            base: SendPtr(base),
+            _ref: PhantomData,
+        }
+    }
+    /// Returns the number of elements in the array.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+    /// Returns `true` if the array has a length of 0.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+    /// Call `f` on the pointer to each element of the array, and return a parallel iterator over
+    /// the results.
+    #[inline]
+    pub fn map<R, F>(self, f: F) -> impl 'a + IndexedParallelIterator<Item = R>
+    where
+        R: Send,
+        F: 'a + Fn(P) -> R + Send + Sync,
+    {
+        let base = self.base;
+        (0..N).into_par_iter().map(move |index| {
+            // SAFETY: the index is in bounds, and the caller of `RegArray::par_iter` promised that
+            // the elements can be accessed from multiple threads
+            f(unsafe { P::from_nonnull(base.get().add(index)) })
+        })
+    }
+    /// Call `f` on the pointer to each element of the array, in parallel.
+    #[inline]
+    pub fn for_each<F>(self, f: F)
+    where
+        F: Fn(P) + Send + Sync,
+    {
+        let base = self.base;
+        (0..N).into_par_iter().for_each(move |index| {
+            // SAFETY: same as in `map`
+            f(unsafe { P::from_nonnull(base.get().add(index)) })
+        })
+    }
+}
+
+/// A pointer that can be sent across threads, see [`RegArray::par_iter`].
+struct SendPtr<T>(NonNull<T>);
+
+impl<T> SendPtr<T> {
+    // a method, rather than a field access, so that closures capture the whole `SendPtr`
+    #[inline]
+    fn get(self) -> NonNull<T> {
+        self.0
+    }
+}
+impl<T> Clone for SendPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for SendPtr<T> {}
+
+// SAFETY: the caller of `RegArray::par_iter` promised that the array can be accessed from multiple
+// threads
+unsafe impl<T> Send for SendPtr<T> {}
+unsafe impl<T> Sync for SendPtr<T> {}
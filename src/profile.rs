@@ -0,0 +1,44 @@
+//! Per-register access counters, to find the hot registers of a driver.
+//!
+//! With the `profile` feature, every [`Reg::read`] and [`Reg::write`] (and the other accessors of
+//! [`Reg`] built on them) increments a counter in a global table keyed by the address of the
+//! register. The counters can be queried with [`counts`] and cleared with [`reset`].
+//!
+//! Requires the `profile` feature, which enables the `std` feature.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+#[cfg(doc)]
+use crate::Reg;
+
+/// Number of reads and writes, keyed by the address of the register.
+static COUNTS: Mutex<BTreeMap<usize, (u64, u64)>> = Mutex::new(BTreeMap::new());
+
+/// Returns the number of reads and writes performed on the register at address `addr`, as a
+/// tuple `(reads, writes)`.
+///
+/// The address is the same as [`as_ptr() as usize`](Reg::as_ptr).
+pub fn counts(addr: usize) -> (u64, u64) {
+    lock().get(&addr).copied().unwrap_or_default()
+}
+
+/// Resets all the counters to zero.
+pub fn reset() {
+    lock().clear()
+}
+
+#[inline]
+pub(crate) fn record_read(addr: usize) {
+    lock().entry(addr).or_default().0 += 1;
+}
+
+#[inline]
+pub(crate) fn record_write(addr: usize) {
+    lock().entry(addr).or_default().1 += 1;
+}
+
+fn lock() -> std::sync::MutexGuard<'static, BTreeMap<usize, (u64, u64)>> {
+    // the table stays consistent even if a thread panicked while holding the lock
+    COUNTS.lock().unwrap_or_else(|err| err.into_inner())
+}
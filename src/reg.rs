@@ -1,14 +1,82 @@
 use core::marker::PhantomData;
+use core::mem::MaybeUninit;
 use core::ptr::NonNull;
 
-use crate::access::{self, Access};
-use crate::integers::Integer;
+use crate::access::{self, Access, ReadWrite, Write1Clear};
+use crate::integers::private::Sealed;
+#[cfg(feature = "use-atomics")]
+use crate::integers::{private::SealedAtomic, AtomicInteger};
+use crate::integers::{Integer, RegEnum, RegValue};
+#[cfg(feature = "use-atomics")]
+use core::sync::atomic::Ordering;
 
 #[cfg(doc)]
-use crate::access::{ReadOnly, ReadWrite, WriteOnly};
+use crate::access::{ReadOnly, WriteOnly};
 #[cfg(doc)]
 use crate::RegMap;
 
+/// The instruction sequence used to read and write a register, selected per call with
+/// [`Reg::read_with`] and [`Reg::write_with`].
+///
+/// [`Reg::read`] and [`Reg::write`] use the [`DefaultVolatile`] flavor. Platform crates can
+/// implement this trait on their own marker types to access registers with custom sequences,
+/// e.g. inline assembly for targets that distinguish between memory types at the instruction
+/// level:
+/// ```
+/// use core::ptr::NonNull;
+/// use reg_map::VolatileOp;
+///
+/// /// Accesses followed by a compiler fence.
+/// #[derive(Clone, Copy)]
+/// struct Fenced;
+///
+/// impl VolatileOp<u32> for Fenced {
+///     unsafe fn read(ptr: NonNull<u32>) -> u32 {
+///         let val = unsafe { ptr.read_volatile() };
+///         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+///         val
+///     }
+///     unsafe fn write(ptr: NonNull<u32>, val: u32) {
+///         unsafe { ptr.write_volatile(val) };
+///         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+///     }
+/// }
+/// ```
+pub trait VolatileOp<T: Integer> {
+    /// Perform a read of the register at `ptr`.
+    ///
+    /// # Safety
+    /// `ptr` must be [valid for reads](core::ptr::read_volatile#safety) and properly aligned.
+    unsafe fn read(ptr: NonNull<T>) -> T;
+
+    /// Perform a write of `val` to the register at `ptr`.
+    ///
+    /// # Safety
+    /// `ptr` must be [valid for writes](core::ptr::write_volatile#safety) and properly aligned.
+    unsafe fn write(ptr: NonNull<T>, val: T);
+}
+
+/// The default [`VolatileOp`] flavor, used by [`Reg::read`] and [`Reg::write`].
+///
+/// Accesses are volatile reads and writes or, with the `use-atomics` feature, relaxed atomic
+/// loads and stores where possible.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DefaultVolatile;
+
+impl<T: Integer> VolatileOp<T> for DefaultVolatile {
+    #[inline(always)]
+    unsafe fn read(ptr: NonNull<T>) -> T {
+        // SAFETY: the caller promises the pointer is valid for reads
+        unsafe { Sealed::load(ptr) }
+    }
+
+    #[inline(always)]
+    unsafe fn write(ptr: NonNull<T>, val: T) {
+        // SAFETY: the caller promises the pointer is valid for writes
+        unsafe { Sealed::store(ptr, val) }
+    }
+}
+
 /// A pointer to a register with volatile reads and writes.
 ///
 /// # Access permissions
@@ -19,12 +87,29 @@ use crate::RegMap;
 ///
 /// Access permissions are defined by the derive macro [`RegMap`] using the `#[reg()]` attribute,
 /// see [Access permissions](crate#access-permissions) in the crate documentation.
-pub struct Reg<'a, T, A> {
-    ptr: NonNull<T>,
-    _ref: PhantomData<&'a T>,
-    _acs: PhantomData<A>,
+///
+/// # Register values
+/// The register stores an [`Integer`] of type `T::Bits`, and the values read and written are of
+/// type `T`. For integer registers, `T` and `T::Bits` are the same type. See [`RegValue`] for
+/// custom register values.
+///
+/// # Atomic accesses
+/// With the `use-atomics` feature, reads and writes are performed as relaxed atomic loads and
+/// stores where possible. See [The `use-atomics` feature](crate#the-use-atomics-feature) in the
+/// crate documentation.
+///
+/// # 128-bit registers
+/// Reads and writes of `u128` and `i128` registers are single volatile accesses at the language
+/// level, but most targets have no general-purpose 128-bit load or store instruction: on e.g.
+/// `x86_64` the compiler splits each access into two 64-bit accesses. The order of the two halves
+/// is unspecified, and the access is not atomic. If the hardware requires a specific
+/// access sequence, model the register as two 64-bit halves instead.
+pub struct Reg<'a, T: RegValue, A> {
+    ptr: NonNull<T::Bits>,
+    _ref: PhantomData<&'a T::Bits>,
+    _acs: PhantomData<(T, A)>,
 }
-impl<'a, T: Integer, A: Access> Reg<'a, T, A> {
+impl<'a, T: RegValue, A: Access> Reg<'a, T, A> {
     /// Creates a new `Reg`.
     ///
     /// ⚠️ This function is called by the field-access methods defined by the derive macro
@@ -36,32 +121,156 @@ impl<'a, T: Integer, A: Access> Reg<'a, T, A> {
     /// - `ptr` must be [valid for writes](core::ptr::write_volatile#safety) if `A: Writable`,
     /// - `ptr` must be properly aligned;
     /// - `ptr` must be valid for the whole lifetime `'a`.
+    ///
+    /// In debug builds, panics if `ptr` is not properly aligned.
     #[doc(hidden)]
     #[allow(non_snake_case)]
     #[inline]
-    pub const unsafe fn __MACRO_ONLY__from_ptr(ptr: *mut T) -> Self {
+    #[track_caller]
+    pub unsafe fn __MACRO_ONLY__from_ptr(ptr: *mut T::Bits) -> Self {
+        debug_assert!(ptr.is_aligned(), "misaligned register pointer");
         Self::from_nonnull(NonNull::new_unchecked(ptr))
     }
     #[inline]
-    pub(crate) const unsafe fn from_nonnull(ptr: NonNull<T>) -> Self {
+    pub(crate) const unsafe fn from_nonnull(ptr: NonNull<T::Bits>) -> Self {
         Self {
             ptr,
             _ref: PhantomData,
             _acs: PhantomData,
         }
     }
+    /// Returns `true` if the register can be read from, i.e. if `A` implements
+    /// [`Readable`](access::Readable).
+    ///
+    /// This is useful e.g. for generic tooling like register browsers, see also
+    /// [`Access::READABLE`].
+    #[inline]
+    pub const fn is_readable() -> bool {
+        A::READABLE
+    }
+    /// Returns `true` if the register can be written to, i.e. if `A` implements
+    /// [`Writable`](access::Writable).
+    ///
+    /// This is useful e.g. for generic tooling like register browsers, see also
+    /// [`Access::WRITABLE`].
+    #[inline]
+    pub const fn is_writable() -> bool {
+        A::WRITABLE
+    }
     /// Returns a raw pointer to the underlying register.
     #[inline]
-    pub const fn as_ptr(&self) -> *mut T {
+    pub const fn as_ptr(&self) -> *mut T::Bits {
         self.ptr.as_ptr()
     }
+    /// Returns a raw pointer to the underlying register, meant to be handed to a DMA controller.
+    ///
+    /// This is the same pointer returned by [`Reg::as_ptr`].
+    ///
+    /// # DMA transfers
+    /// Memory accesses performed by a DMA controller are invisible to the compiler. The caller is
+    /// responsible for:
+    /// - ensuring that the transfer completes before the end of the lifetime `'a`;
+    /// - ensuring that no conflicting accesses are performed through this `Reg` while the
+    ///   transfer is in progress;
+    /// - performing any fence and cache maintenance required by the platform before starting and
+    ///   after completing the transfer.
+    #[inline]
+    pub const fn dma_ptr(&self) -> *mut T::Bits {
+        self.as_ptr()
+    }
+    /// Returns `true` if the register is located at address `addr`.
+    #[inline]
+    pub fn eq_addr(&self, addr: *const T::Bits) -> bool {
+        core::ptr::eq(self.as_ptr(), addr)
+    }
     /// Perform a volatile read.
     #[inline]
     pub fn read(&self) -> T
     where
         A: access::Readable,
     {
-        unsafe { self.ptr.read_volatile() }
+        self.read_with(DefaultVolatile)
+    }
+    /// Perform a read with the access flavor `O`, see [`VolatileOp`].
+    ///
+    /// `self.read_with(DefaultVolatile)` is the same as `self.read()`.
+    #[inline]
+    pub fn read_with<O: VolatileOp<T::Bits>>(&self, _op: O) -> T
+    where
+        A: access::Readable,
+    {
+        #[cfg(feature = "profile")]
+        crate::profile::record_read(self.as_ptr() as usize);
+        // SAFETY: the pointer is valid for reads, see `__MACRO_ONLY__from_ptr`
+        T::from_bits(unsafe { O::read(self.ptr) })
+    }
+    /// Perform a volatile read, and return the address of the register alongside the value.
+    ///
+    /// The address is the same as [`as_ptr() as usize`](Reg::as_ptr). This is useful to build
+    /// transaction logs at the application level.
+    #[inline]
+    pub fn read_logged(&self) -> (usize, T)
+    where
+        A: access::Readable,
+    {
+        (self.as_ptr() as usize, self.read())
+    }
+    /// Perform a volatile read of the version register `version`, then a volatile read of this
+    /// register, and return both values.
+    ///
+    /// This is meant for double-buffered registers paired with a version counter that the
+    /// hardware increments on every update. The caller can detect a stale read by reading
+    /// `version` again, and retrying if it changed:
+    /// ```
+    /// # use reg_map::{access::ReadOnly, Reg};
+    /// fn read_consistent(data: &Reg<u64, ReadOnly>, version: &Reg<u32, ReadOnly>) -> u64 {
+    ///     loop {
+    ///         let (before, val) = data.read_versioned(version);
+    ///         if version.read() == before {
+    ///             return val;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn read_versioned<V: access::Readable>(&self, version: &Reg<'_, u32, V>) -> (u32, T)
+    where
+        A: access::Readable,
+    {
+        let before = version.read();
+        (before, self.read())
+    }
+    /// Perform two volatile reads, and return `Ok` with the value if both reads agree, or `Err`
+    /// with both values otherwise.
+    ///
+    /// This is meant for hardware bring-up diagnostics: reading a register that is expected to
+    /// be stable twice can detect flaky buses or incorrect address decoding.
+    #[inline]
+    pub fn read_twice_check(&self) -> Result<T, (T, T)>
+    where
+        A: access::Readable,
+        T: PartialEq,
+    {
+        let first = self.read();
+        let second = self.read();
+        if first == second {
+            Ok(first)
+        } else {
+            Err((first, second))
+        }
+    }
+    /// Perform one volatile read from the register for each element of `dst`.
+    ///
+    /// All reads are performed on the same address, in order. This is useful e.g. to drain a FIFO
+    /// whose read port is a single register.
+    #[inline]
+    pub fn read_each(&self, dst: &mut [T])
+    where
+        A: access::Readable,
+    {
+        for val in dst {
+            *val = self.read();
+        }
     }
     /// Perform a volatile write.
     #[inline]
@@ -69,7 +278,760 @@ impl<'a, T: Integer, A: Access> Reg<'a, T, A> {
     where
         A: access::Writable,
     {
-        unsafe { self.ptr.write_volatile(val) }
+        self.write_with(DefaultVolatile, val)
+    }
+    /// Perform a write with the access flavor `O`, see [`VolatileOp`].
+    ///
+    /// `self.write_with(DefaultVolatile, val)` is the same as `self.write(val)`.
+    #[inline]
+    pub fn write_with<O: VolatileOp<T::Bits>>(&self, _op: O, val: T)
+    where
+        A: access::Writable,
+    {
+        #[cfg(feature = "profile")]
+        crate::profile::record_write(self.as_ptr() as usize);
+        // SAFETY: the pointer is valid for writes, see `__MACRO_ONLY__from_ptr`
+        unsafe { O::write(self.ptr, val.to_bits()) }
+    }
+    /// Perform a volatile write of a value convertible into `T`.
+    ///
+    /// This is [`Reg::write`] with an [`Into`] conversion, e.g. to write a `u8` to a `u32`
+    /// register. It is a separate method so that `write` keeps inferring the type of its argument
+    /// from the register.
+    #[inline]
+    pub fn write_from<V: Into<T>>(&self, val: V)
+    where
+        A: access::Writable,
+    {
+        self.write(val.into())
+    }
+    /// Perform a volatile read, apply `f` to the value, perform a volatile write of the result,
+    /// and return the written value.
+    ///
+    /// ⚠️ The read and the write are two separate volatile accesses: this operation is *not*
+    /// atomic.
+    #[inline]
+    pub fn update<F: FnOnce(T) -> T>(&self, f: F) -> T
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        let val = f(self.read());
+        self.write(val);
+        val
+    }
+    /// Perform a volatile read, apply `f` to the value, and perform a volatile write of the
+    /// result.
+    ///
+    /// No reference to the underlying memory is created: the register is accessed only through
+    /// its raw pointer, as in [`Reg::read`] and [`Reg::write`].
+    ///
+    /// ⚠️ The read and the write are two separate volatile accesses: this operation is *not*
+    /// atomic, and is unsuitable for registers that are concurrently modified, e.g. by another
+    /// core or by an interrupt handler.
+    ///
+    /// The method is only available on read-write registers. The following code does not
+    /// compile:
+    /// ```compile_fail,E0277
+    /// # mod yoo {
+    /// # use reg_map::RegMap;
+    /// # #[repr(C)]
+    /// # #[derive(RegMap, Default)]
+    /// # pub struct Registers {
+    /// #     #[reg(RO)]
+    /// #     pub status: u32,
+    /// # }
+    /// # } // mod yoo
+    /// # use yoo::{Registers, RegistersPtr};
+    /// # let mut regs = Registers::default();
+    /// # let ptr = RegistersPtr::from_mut(&mut regs);
+    /// ptr.status().modify(|v| v | 1); // error[E0277]: cannot write to a read-only register
+    /// ```
+    /// Nor does the following:
+    /// ```compile_fail,E0277
+    /// # mod yoo {
+    /// # use reg_map::RegMap;
+    /// # #[repr(C)]
+    /// # #[derive(RegMap, Default)]
+    /// # pub struct Registers {
+    /// #     #[reg(WO)]
+    /// #     pub command: u32,
+    /// # }
+    /// # } // mod yoo
+    /// # use yoo::{Registers, RegistersPtr};
+    /// # let mut regs = Registers::default();
+    /// # let ptr = RegistersPtr::from_mut(&mut regs);
+    /// ptr.command().modify(|v| v | 1); // error[E0277]: cannot read from a write-only register
+    /// ```
+    #[inline]
+    pub fn modify<F: FnOnce(T) -> T>(&self, f: F)
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        self.write(f(self.read()))
+    }
+    /// Read the value of the register and apply `f` to it: if `f` returns `Some(new)`, write
+    /// `new` and return `Ok` with the previous value, otherwise return `Err` with the current
+    /// value.
+    ///
+    /// This mirrors [`AtomicU32::fetch_update`](core::sync::atomic::AtomicU32::fetch_update),
+    /// and unifies conditional updates of a register.
+    ///
+    /// ⚠️ Without the `use-atomics` feature, the read and the write are two separate volatile
+    /// accesses: this operation is *not* atomic. With the feature, the update is performed as a
+    /// relaxed atomic operation where available, and `f` may be called multiple times if the
+    /// register is concurrently modified. See
+    /// [The `use-atomics` feature](crate#the-use-atomics-feature) in the crate documentation.
+    #[inline]
+    pub fn fetch_update<F: FnMut(T) -> Option<T>>(&self, mut f: F) -> Result<T, T>
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        let res = unsafe {
+            Sealed::fetch_update(self.ptr, |bits| f(T::from_bits(bits)).map(T::to_bits))
+        };
+        #[cfg(feature = "profile")]
+        self.record_update(res.is_ok());
+        res.map(T::from_bits).map_err(T::from_bits)
+    }
+    /// Counts a read, and a write if `written`, with the `profile` feature.
+    #[cfg(feature = "profile")]
+    #[inline]
+    fn record_update(&self, written: bool) {
+        crate::profile::record_read(self.as_ptr() as usize);
+        if written {
+            crate::profile::record_write(self.as_ptr() as usize);
+        }
+    }
+}
+
+impl<'a, T: Integer, A: Access> Reg<'a, T, A> {
+    /// Perform a volatile read, and return the value as its non-zero counterpart.
+    ///
+    /// Returns `None` if the value read is zero. This is useful for registers that are known to
+    /// be non-zero, e.g. identification registers.
+    #[inline]
+    pub fn read_nonzero(&self) -> Option<T::NonZero>
+    where
+        A: access::Readable,
+    {
+        T::NonZero::try_from(self.read()).ok()
+    }
+    /// Perform a volatile read, and convert the value to the enum `E`.
+    ///
+    /// Returns `None` if the value read does not correspond to any variant of `E`, e.g. because
+    /// the hardware returned an undocumented value.
+    #[inline]
+    pub fn read_enum<E: RegEnum<Repr = T>>(&self) -> Option<E>
+    where
+        A: access::Readable,
+    {
+        E::from_repr(self.read())
+    }
+    /// Perform a volatile write of the integer value of the enum variant `e`.
+    #[inline]
+    pub fn write_enum<E: RegEnum<Repr = T>>(&self, e: E)
+    where
+        A: access::Writable,
+    {
+        self.write(e.to_repr())
+    }
+    /// Perform a volatile read, and return the value of bit `n`.
+    ///
+    /// # Panics
+    /// If `n` is out of range, i.e. if `n >= T::BITS`.
+    #[inline]
+    #[track_caller]
+    pub fn get_bit(&self, n: u32) -> bool
+    where
+        A: access::Readable,
+    {
+        self.read() & bit_mask::<T>(n) != T::ZERO
+    }
+    /// Perform a volatile read, set bit `n`, and perform a volatile write of the result.
+    ///
+    /// ⚠️ The read and the write are two separate volatile accesses: this operation is *not*
+    /// atomic.
+    ///
+    /// # Panics
+    /// If `n` is out of range, i.e. if `n >= T::BITS`.
+    #[inline]
+    #[track_caller]
+    pub fn set_bit(&self, n: u32)
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        let mask = bit_mask::<T>(n);
+        self.write(self.read() | mask)
+    }
+    /// Perform a volatile read, clear bit `n`, and perform a volatile write of the result.
+    ///
+    /// ⚠️ The read and the write are two separate volatile accesses: this operation is *not*
+    /// atomic.
+    ///
+    /// # Panics
+    /// If `n` is out of range, i.e. if `n >= T::BITS`.
+    #[inline]
+    #[track_caller]
+    pub fn clear_bit(&self, n: u32)
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        let mask = bit_mask::<T>(n);
+        self.write(self.read() & !mask)
+    }
+    /// Perform a volatile read, flip bit `n`, and perform a volatile write of the result.
+    ///
+    /// ⚠️ The read and the write are two separate volatile accesses: this operation is *not*
+    /// atomic.
+    ///
+    /// # Panics
+    /// If `n` is out of range, i.e. if `n >= T::BITS`.
+    #[inline]
+    #[track_caller]
+    pub fn toggle_bit(&self, n: u32)
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        let mask = bit_mask::<T>(n);
+        self.write(self.read() ^ mask)
+    }
+    /// Perform a volatile read, and return the bit field of `width` bits starting at bit `lsb`,
+    /// shifted down to bit 0.
+    ///
+    /// E.g. `read_field(4, 4)` returns bits `4..=7` of the register.
+    ///
+    /// # Panics
+    /// If the bit field is out of range, i.e. if `lsb + width > T::BITS`.
+    #[inline]
+    #[track_caller]
+    pub fn read_field(&self, lsb: u32, width: u32) -> T
+    where
+        A: access::Readable,
+    {
+        let mask = field_mask::<T>(lsb, width);
+        let val = self.read();
+        if width == 0 {
+            T::ZERO
+        } else {
+            (val >> lsb) & mask
+        }
+    }
+    /// Perform a volatile read, replace the bit field of `width` bits starting at bit `lsb` with
+    /// `value`, and perform a volatile write of the result.
+    ///
+    /// All bits outside of the bit field are written back unchanged. Bits of `value` above
+    /// `width` are ignored.
+    ///
+    /// ⚠️ The read and the write are two separate volatile accesses: this operation is *not*
+    /// atomic.
+    ///
+    /// # Panics
+    /// If the bit field is out of range, i.e. if `lsb + width > T::BITS`.
+    #[inline]
+    #[track_caller]
+    pub fn write_field(&self, lsb: u32, width: u32, value: T)
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        let mask = field_mask::<T>(lsb, width);
+        let val = self.read();
+        if width == 0 {
+            self.write(val)
+        } else {
+            self.write((val & !(mask << lsb)) | ((value & mask) << lsb))
+        }
+    }
+    /// Perform a volatile read, and return `true` if the value read is zero, i.e. the default
+    /// value of `T`.
+    ///
+    /// This is useful e.g. to check that a register is in its reset state during initialization.
+    #[inline]
+    pub fn is_zero(&self) -> bool
+    where
+        A: access::Readable,
+    {
+        self.read() == T::ZERO
+    }
+    /// Perform a volatile read, and return `Err` if the value read is equal to `unmapped`.
+    ///
+    /// This is useful during bring-up to catch accesses to unimplemented registers, which by
+    /// convention often read as a sentinel value, e.g. all ones.
+    #[allow(clippy::result_unit_err)]
+    #[inline]
+    pub fn read_checked(&self, unmapped: T) -> Result<T, ()>
+    where
+        A: access::Readable,
+    {
+        let val = self.read();
+        if val == unmapped {
+            Err(())
+        } else {
+            Ok(val)
+        }
+    }
+    /// Perform a volatile read, and return the value with all bits outside of `defined_mask`
+    /// cleared, i.e. `read() & defined_mask`.
+    ///
+    /// This is useful for registers where only some bits are defined, to compare the value read
+    /// without false mismatches caused by undefined or reserved bits.
+    #[inline]
+    pub fn read_defined(&self, defined_mask: T) -> T
+    where
+        A: access::Readable,
+    {
+        self.read() & defined_mask
+    }
+    /// Perform a volatile read, and return an iterator over the indices of the bits set in the
+    /// value read, from the least significant.
+    ///
+    /// The register is read only once, when calling this method. This is useful e.g. to dispatch
+    /// all pending interrupts in an interrupt-status register.
+    #[inline]
+    pub fn set_bits_iter(&self) -> impl Iterator<Item = u32>
+    where
+        A: access::Readable,
+    {
+        let mut val = self.read();
+        core::iter::from_fn(move || {
+            if val == T::ZERO {
+                None
+            } else {
+                let index = val.trailing_zeros();
+                val = val ^ (T::ONE << index);
+                Some(index)
+            }
+        })
+    }
+    /// Perform a volatile write of a value with all bytes equal to `byte`.
+    ///
+    /// This is the equivalent of `memset` over the whole register, performed with a single volatile
+    /// write.
+    #[inline]
+    pub fn write_bytes(&self, byte: u8)
+    where
+        A: access::Writable,
+    {
+        let mut val = MaybeUninit::<T>::uninit();
+        // SAFETY: all bytes are initialized, and any bit pattern is a valid integer
+        let val = unsafe {
+            val.as_mut_ptr().write_bytes(byte, 1);
+            val.assume_init()
+        };
+        self.write(val)
+    }
+}
+
+impl<'a, T: RegValue> Reg<'a, T, Write1Clear> {
+    /// Clear the flags set in `mask`, with a single volatile write of `mask`.
+    ///
+    /// The register has write-1-to-clear semantics: the flags set in `mask` are cleared, and the
+    /// other flags are left unchanged, since zeros are written to them. The register is not read
+    /// before the write, and no read-modify-write is performed.
+    #[inline]
+    pub fn clear_flags(&self, mask: T) {
+        self.write(mask)
+    }
+}
+
+/// Returns the mask of bit `n`, checking that `n` is in range for `T`.
+#[inline]
+#[track_caller]
+fn bit_mask<T: Integer>(n: u32) -> T {
+    assert!(
+        n < T::BITS,
+        "bit index {n} out of range for {} bits",
+        T::BITS
+    );
+    T::ONE << n
+}
+
+/// Returns the mask of a bit field of `width` bits, checking that the bit field starting at bit
+/// `lsb` is in range for `T`.
+#[inline]
+#[track_caller]
+fn field_mask<T: Integer>(lsb: u32, width: u32) -> T {
+    assert!(
+        width <= T::BITS && lsb <= T::BITS - width,
+        "bit field of width {width} at bit {lsb} out of range for {} bits",
+        T::BITS
+    );
+    if width == T::BITS {
+        !T::ZERO
+    } else {
+        !(!T::ZERO << width)
+    }
+}
+
+#[cfg(feature = "compat")]
+impl<'a, T: RegValue, A: Access> Reg<'a, T, A> {
+    /// Perform a volatile read.
+    ///
+    /// Alias of [`Reg::read`], mimicking `VolatileCell::get` to ease migration from the
+    /// `volatile-register` crate.
+    #[inline]
+    pub fn get(&self) -> T
+    where
+        A: access::Readable,
+    {
+        self.read()
+    }
+    /// Perform a volatile write.
+    ///
+    /// Alias of [`Reg::write`], mimicking `VolatileCell::set` to ease migration from the
+    /// `volatile-register` crate.
+    #[inline]
+    pub fn set(&self, val: T)
+    where
+        A: access::Writable,
+    {
+        self.write(val)
+    }
+}
+
+#[cfg(feature = "fixed")]
+impl<'a, T: Integer, A: Access> Reg<'a, T, A> {
+    /// Perform a volatile read, and return the value as a fixed-point number of the `fixed` crate.
+    ///
+    /// The bits of the register are reinterpreted as the fixed-point type `F`, whose underlying
+    /// integer must be the register type, e.g. `FixedU32<U16>` for a Q16.16 value in a [`u32`]
+    /// register.
+    ///
+    /// Requires the `fixed` feature.
+    #[inline]
+    pub fn read_fixed<F: fixed::traits::Fixed<Bits = T>>(&self) -> F
+    where
+        A: access::Readable,
+    {
+        F::from_bits(self.read())
+    }
+    /// Perform a volatile write of the bits of the fixed-point number `val`.
+    ///
+    /// The underlying integer of `F` must be the register type, see [`Reg::read_fixed`].
+    ///
+    /// Requires the `fixed` feature.
+    #[inline]
+    pub fn write_fixed<F: fixed::traits::Fixed<Bits = T>>(&self, val: F)
+    where
+        A: access::Writable,
+    {
+        self.write(val.to_bits())
+    }
+}
+
+#[cfg(feature = "use-atomics")]
+impl<'a, T: AtomicInteger, A: Access> Reg<'a, T, A> {
+    /// Perform an atomic compare-and-exchange: stores `new` if the register contains `current`.
+    ///
+    /// Returns `Ok` with the previous value on success, and `Err` with the current value on
+    /// failure. The orderings have the same meaning as in
+    /// [`AtomicU32::compare_exchange`](core::sync::atomic::AtomicU32::compare_exchange).
+    ///
+    /// Requires the `use-atomics` feature, see
+    /// [The `use-atomics` feature](crate#the-use-atomics-feature) in the crate documentation.
+    ///
+    /// # Panics
+    /// If `failure` is [`Release`](Ordering::Release) or [`AcqRel`](Ordering::AcqRel), or if the
+    /// register is not aligned for the atomic type.
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: T,
+        new: T,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<T, T>
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        let res =
+            unsafe { SealedAtomic::compare_exchange(self.ptr, current, new, success, failure) };
+        #[cfg(feature = "profile")]
+        self.record_update(res.is_ok());
+        res
+    }
+    /// Perform an atomic bitwise OR with `val`, setting the bits of `val`, and return the previous value.
+    ///
+    /// The ordering has the same meaning as in
+    /// [`AtomicU32::fetch_or`](core::sync::atomic::AtomicU32::fetch_or).
+    ///
+    /// Requires the `use-atomics` feature, see
+    /// [The `use-atomics` feature](crate#the-use-atomics-feature) in the crate documentation.
+    ///
+    /// # Panics
+    /// If the register is not aligned for the atomic type.
+    #[inline]
+    pub fn fetch_or(&self, val: T, order: Ordering) -> T
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        #[cfg(feature = "profile")]
+        self.record_update(true);
+        unsafe { SealedAtomic::fetch_or(self.ptr, val, order) }
+    }
+    /// Perform an atomic bitwise AND with `val`, clearing the bits not set in `val`, and return the previous value.
+    ///
+    /// The ordering has the same meaning as in
+    /// [`AtomicU32::fetch_and`](core::sync::atomic::AtomicU32::fetch_and).
+    ///
+    /// Requires the `use-atomics` feature, see
+    /// [The `use-atomics` feature](crate#the-use-atomics-feature) in the crate documentation.
+    ///
+    /// # Panics
+    /// If the register is not aligned for the atomic type.
+    #[inline]
+    pub fn fetch_and(&self, val: T, order: Ordering) -> T
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        #[cfg(feature = "profile")]
+        self.record_update(true);
+        unsafe { SealedAtomic::fetch_and(self.ptr, val, order) }
+    }
+    /// Perform an atomic bitwise XOR with `val`, toggling the bits of `val`, and return the previous value.
+    ///
+    /// The ordering has the same meaning as in
+    /// [`AtomicU32::fetch_xor`](core::sync::atomic::AtomicU32::fetch_xor).
+    ///
+    /// Requires the `use-atomics` feature, see
+    /// [The `use-atomics` feature](crate#the-use-atomics-feature) in the crate documentation.
+    ///
+    /// # Panics
+    /// If the register is not aligned for the atomic type.
+    #[inline]
+    pub fn fetch_xor(&self, val: T, order: Ordering) -> T
+    where
+        A: access::Readable + access::Writable + access::Modifiable,
+    {
+        #[cfg(feature = "profile")]
+        self.record_update(true);
+        unsafe { SealedAtomic::fetch_xor(self.ptr, val, order) }
+    }
+    /// Perform an atomic load with [`Acquire`](Ordering::Acquire) ordering.
+    ///
+    /// Pairs with [`Reg::store_release`] for producer/consumer handshakes: all memory accesses
+    /// performed before the store-release that wrote the loaded value are visible after this call.
+    ///
+    /// Requires the `use-atomics` feature, see
+    /// [The `use-atomics` feature](crate#the-use-atomics-feature) in the crate documentation.
+    ///
+    /// # Panics
+    /// If the register is not aligned for the atomic type.
+    #[inline]
+    pub fn load_acquire(&self) -> T
+    where
+        A: access::Readable,
+    {
+        #[cfg(feature = "profile")]
+        crate::profile::record_read(self.as_ptr() as usize);
+        unsafe { SealedAtomic::atomic_load(self.ptr, Ordering::Acquire) }
+    }
+    /// Perform an atomic store with [`Release`](Ordering::Release) ordering.
+    ///
+    /// Pairs with [`Reg::load_acquire`] for producer/consumer handshakes: all memory accesses
+    /// performed before this call are visible after a load-acquire that reads the stored value.
+    ///
+    /// Requires the `use-atomics` feature, see
+    /// [The `use-atomics` feature](crate#the-use-atomics-feature) in the crate documentation.
+    ///
+    /// # Panics
+    /// If the register is not aligned for the atomic type.
+    #[inline]
+    pub fn store_release(&self, val: T)
+    where
+        A: access::Writable,
+    {
+        #[cfg(feature = "profile")]
+        crate::profile::record_write(self.as_ptr() as usize);
+        unsafe { SealedAtomic::atomic_store(self.ptr, val, Ordering::Release) }
+    }
+}
+
+/// A pointer to a register declared with a non-zero integer type, e.g. `NonZeroU32`, storing an
+/// integer of type `T`.
+///
+/// This struct is created by the derive macro [`RegMap`] for fields of non-zero type, see
+/// [Non-zero registers](crate#non-zero-registers) in the crate documentation. Reads return
+/// `None` if the hardware returns zero, and only non-zero values can be written, so that a
+/// register map backed by ordinary memory, e.g. through `from_mut`, always holds valid values.
+///
+/// Access permissions work the same as for [`Reg`].
+pub struct RegNonZero<'a, T: Integer, A> {
+    reg: Reg<'a, T, A>,
+}
+impl<'a, T: Integer, A: Access> RegNonZero<'a, T, A> {
+    /// Creates a new `RegNonZero`.
+    ///
+    /// ⚠️ This function is called by the field-access methods defined by the derive macro
+    /// [`RegMap`]. Do *not* call this function directly. Changes to this function are not
+    /// considered semver breaking.
+    ///
+    /// # Safety
+    /// - `ptr` must be [valid for reads](core::ptr::read_volatile#safety) if `A: Readable`,
+    /// - `ptr` must be [valid for writes](core::ptr::write_volatile#safety) if `A: Writable`,
+    /// - `ptr` must be properly aligned;
+    /// - `ptr` must be valid for the whole lifetime `'a`.
+    ///
+    /// In debug builds, panics if `ptr` is not properly aligned.
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[inline]
+    #[track_caller]
+    pub unsafe fn __MACRO_ONLY__from_ptr(ptr: *mut T) -> Self {
+        Self {
+            reg: Reg::__MACRO_ONLY__from_ptr(ptr),
+        }
+    }
+    #[inline]
+    pub(crate) const unsafe fn from_nonnull(ptr: NonNull<T>) -> Self {
+        Self {
+            reg: Reg::from_nonnull(ptr),
+        }
+    }
+    /// Returns a raw pointer to the underlying register.
+    #[inline]
+    pub const fn as_ptr(&self) -> *mut T {
+        self.reg.as_ptr()
+    }
+    /// Perform a volatile read, and return `None` if the value read is zero.
+    #[inline]
+    pub fn read(&self) -> Option<T::NonZero>
+    where
+        A: access::Readable,
+    {
+        self.reg.read_nonzero()
+    }
+    /// Perform a volatile write of a non-zero value.
+    #[inline]
+    pub fn write(&self, val: T::NonZero)
+    where
+        A: access::Writable,
+    {
+        self.reg.write(val.into())
+    }
+}
+
+/// A pointer to a register storing a byte array `[u8; N]`, read and written as a single unit.
+///
+/// This struct is created by the derive macro [`RegMap`] for fields of type `[u8; N]` annotated
+/// with `#[reg(bytes)]`, instead of a [`RegArray`](crate::RegArray) of `N` one-byte registers.
+/// See [Byte-array registers](crate#byte-array-registers) in the crate documentation.
+///
+/// Each read and write is a single volatile access of type `[u8; N]`. Whether the compiler emits
+/// a single instruction for it depends on `N` and on the target: like for
+/// [128-bit registers](Reg#128-bit-registers), the access is not atomic.
+///
+/// Access permissions work the same as for [`Reg`].
+pub struct RegBytes<'a, const N: usize, A> {
+    ptr: NonNull<[u8; N]>,
+    _ref: PhantomData<&'a [u8; N]>,
+    _acs: PhantomData<A>,
+}
+impl<'a, const N: usize, A: Access> RegBytes<'a, N, A> {
+    /// Creates a new `RegBytes`.
+    ///
+    /// ⚠️ This function is called by the field-access methods defined by the derive macro
+    /// [`RegMap`]. Do *not* call this function directly. Changes to this function are not
+    /// considered semver breaking.
+    ///
+    /// # Safety
+    /// - `ptr` must be [valid for reads](core::ptr::read_volatile#safety) if `A: Readable`,
+    /// - `ptr` must be [valid for writes](core::ptr::write_volatile#safety) if `A: Writable`,
+    /// - `ptr` must be valid for the whole lifetime `'a`.
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[inline]
+    pub const unsafe fn __MACRO_ONLY__from_ptr(ptr: *mut [u8; N]) -> Self {
+        Self {
+            ptr: NonNull::new_unchecked(ptr),
+            _ref: PhantomData,
+            _acs: PhantomData,
+        }
+    }
+    /// Returns a raw pointer to the underlying register.
+    #[inline]
+    pub const fn as_ptr(&self) -> *mut [u8; N] {
+        self.ptr.as_ptr()
+    }
+    /// Returns the number of bytes in the register.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+    /// Returns `true` if the register has a length of 0.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+    /// Perform a volatile read of all the bytes of the register.
+    #[inline]
+    pub fn read(&self) -> [u8; N]
+    where
+        A: access::Readable,
+    {
+        // SAFETY: the pointer is valid for reads, see `__MACRO_ONLY__from_ptr`
+        unsafe { core::ptr::read_volatile(self.as_ptr()) }
+    }
+    /// Perform a volatile write of all the bytes of the register.
+    #[inline]
+    pub fn write(&self, val: [u8; N])
+    where
+        A: access::Writable,
+    {
+        // SAFETY: the pointer is valid for writes, see `__MACRO_ONLY__from_ptr`
+        unsafe { core::ptr::write_volatile(self.as_ptr(), val) }
+    }
+}
+
+/// Read-write registers, abstracting over real registers and host-side mocks.
+///
+/// This trait is implemented by read-write [`Reg`]s and, with the `std` feature, by
+/// `mock::MockReg`. Driver code taking e.g. an `impl RegisterAccess<u32>` can then be unit-tested
+/// on the host against mock registers.
+pub trait RegisterAccess<T> {
+    /// Read the value of the register.
+    fn read(&self) -> T;
+
+    /// Write the value of the register.
+    fn write(&self, val: T);
+
+    /// Read the value of the register, apply `f` to it, and write the result.
+    ///
+    /// ⚠️ For real registers, the read and the write are two separate volatile accesses: this
+    /// operation is *not* atomic.
+    #[inline]
+    fn modify<F: FnOnce(T) -> T>(&self, f: F) {
+        self.write(f(self.read()))
+    }
+
+    /// Read the value of the register twice, and return `Ok` with the value if both reads agree,
+    /// or `Err` with both values otherwise. See [`Reg::read_twice_check`].
+    #[inline]
+    fn read_twice_check(&self) -> Result<T, (T, T)>
+    where
+        T: PartialEq,
+    {
+        let first = self.read();
+        let second = self.read();
+        if first == second {
+            Ok(first)
+        } else {
+            Err((first, second))
+        }
+    }
+}
+
+impl<'a, T: RegValue> RegisterAccess<T> for Reg<'a, T, ReadWrite> {
+    #[inline]
+    fn read(&self) -> T {
+        Reg::read(self)
+    }
+    #[inline]
+    fn write(&self, val: T) {
+        Reg::write(self, val)
+    }
+    #[inline]
+    fn modify<F: FnOnce(T) -> T>(&self, f: F) {
+        Reg::modify(self, f)
     }
 }
 
@@ -98,11 +1060,81 @@ pub unsafe trait RegMapPtr<'a>: Sized + 'a {
     /// - `ptr` must point to a valid instance of `Self::RegMap`;
     /// - `ptr` must be valid for the whole lifetime `'a`;
     /// - all fields of `Self::RegMap` must allow volatile reads/writes.
+    ///
+    /// In debug builds, panics if `ptr` is not properly aligned.
     unsafe fn from_ptr(ptr: *mut Self::RegMap) -> Self;
 
     /// Return a pointer to `Self::RegMap` from a mutable (exclusive) reference.
     fn from_mut(reg: &'a mut Self::RegMap) -> Self;
 
+    /// Return a pointer to `Self::RegMap` from a shared reference to an
+    /// [`UnsafeCell`](core::cell::UnsafeCell).
+    ///
+    /// This is useful for interoperability with other abstractions that store the register map in
+    /// an `UnsafeCell`. No references to the contents of the cell, e.g. created from
+    /// [`UnsafeCell::get`](core::cell::UnsafeCell::get), can be live while the returned pointer is
+    /// used.
+    #[inline]
+    fn from_unsafe_cell(cell: &'a core::cell::UnsafeCell<Self::RegMap>) -> Self {
+        // safe because `UnsafeCell` allows mutation through a shared reference, and the borrow is
+        // valid for 'a
+        unsafe { Self::from_ptr(cell.get()) }
+    }
+
     /// Returns a raw pointer to the underlying register map.
     fn as_ptr(&self) -> *mut Self::RegMap;
+
+    /// Returns the size in bytes of the underlying register map.
+    ///
+    /// Same as [`size_of::<Self::RegMap>()`](core::mem::size_of).
+    #[inline]
+    fn size(&self) -> usize {
+        core::mem::size_of::<Self::RegMap>()
+    }
+
+    /// Returns the alignment in bytes of the underlying register map.
+    ///
+    /// Same as [`align_of::<Self::RegMap>()`](core::mem::align_of).
+    #[inline]
+    fn align(&self) -> usize {
+        core::mem::align_of::<Self::RegMap>()
+    }
+
+    /// Returns the address located `byte_offset` bytes after the start of the register map.
+    ///
+    /// Combined with the byte-offset constants generated by the derive macro [`RegMap`], this
+    /// computes the absolute address of a field, e.g. to hand it to a DMA controller:
+    /// `ptr.field_addr(FooPtr::FIELD2_OFFSET)`. The address is computed with wrapping arithmetic
+    /// and is never dereferenced.
+    #[inline]
+    fn field_addr(&self, byte_offset: usize) -> *mut u8 {
+        self.as_ptr().cast::<u8>().wrapping_add(byte_offset)
+    }
+
+    /// Returns a read-write register of type `T` located `byte_offset` bytes after the start of
+    /// the register map.
+    ///
+    /// This is an escape hatch to access registers that are not modeled in `Self::RegMap`, e.g.
+    /// undocumented or reserved registers. Prefer the field-access methods defined by the derive
+    /// macro [`RegMap`] whenever possible.
+    ///
+    /// # Safety
+    /// - the address `byte_offset` bytes after [`as_ptr`](RegMapPtr::as_ptr) must be properly
+    ///   aligned for `T`;
+    /// - the register at that address must be [valid for reads](core::ptr::read_volatile#safety)
+    ///   and [valid for writes](core::ptr::write_volatile#safety) of type `T`;
+    /// - the register at that address must be valid for the whole lifetime `'a`.
+    ///
+    /// Note that the returned register is always [`ReadWrite`], regardless of the actual access
+    /// permissions of the hardware: it is up to the caller to only perform allowed accesses.
+    #[inline]
+    unsafe fn reg_at<T: Integer>(&self, byte_offset: usize) -> Reg<'a, T, ReadWrite> {
+        let ptr = self
+            .as_ptr()
+            .cast::<u8>()
+            .wrapping_add(byte_offset)
+            .cast::<T>();
+        // SAFETY: the caller promises the address is valid, thus also not null
+        unsafe { Reg::from_nonnull(NonNull::new_unchecked(ptr)) }
+    }
 }
@@ -0,0 +1,77 @@
+//! Arrays of registers with a shadow buffer of the last-written values.
+
+use crate::access::{self, Access};
+use crate::arr::RegArray;
+use crate::integers::RegValue;
+use crate::reg::Reg;
+
+/// An array of registers paired with a shadow buffer in normal memory.
+///
+/// Every write is performed on both the register and the shadow buffer, so that the last-written
+/// values can be read back from the shadow without any bus traffic. This is useful e.g. for
+/// write-only registers, or for write-heavy access patterns with occasional reads.
+///
+/// This struct is created by the [`with_shadow`](RegArray::with_shadow) method on [`RegArray`].
+///
+/// ⚠️ The shadow buffer is only updated by writes performed through this struct: it goes out of
+/// sync if the registers are written through other pointers, or if they are modified by the
+/// hardware. See [`ShadowArray::refresh`] to synchronize the shadow with the registers.
+pub struct ShadowArray<'a, 's, T: RegValue, A: Access, const N: usize> {
+    arr: RegArray<'a, Reg<'a, T, A>, N>,
+    shadow: &'s mut [T],
+}
+
+impl<'a, 's, T: RegValue + 'a, A: Access, const N: usize> ShadowArray<'a, 's, T, A, N> {
+    #[track_caller]
+    pub(crate) fn new(arr: RegArray<'a, Reg<'a, T, A>, N>, shadow: &'s mut [T]) -> Self {
+        assert_eq!(
+            shadow.len(),
+            N,
+            "shadow buffer length must match the array length"
+        );
+        Self { arr, shadow }
+    }
+    /// Returns the underlying array of registers.
+    #[inline]
+    pub fn array(&self) -> &RegArray<'a, Reg<'a, T, A>, N> {
+        &self.arr
+    }
+    /// Returns the shadow buffer.
+    #[inline]
+    pub fn shadow(&self) -> &[T] {
+        self.shadow
+    }
+    /// Returns the last value written at `index`, from the shadow buffer.
+    ///
+    /// No volatile read is performed.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds, i.e. if `index >= N`.
+    #[inline]
+    #[track_caller]
+    pub fn read(&self, index: usize) -> T {
+        self.shadow[index]
+    }
+    /// Perform a volatile write at `index`, and store the same value in the shadow buffer.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds, i.e. if `index >= N`.
+    #[inline]
+    #[track_caller]
+    pub fn write(&mut self, index: usize, val: T)
+    where
+        A: access::Writable,
+    {
+        self.arr.idx(index).write(val);
+        self.shadow[index] = val;
+    }
+    /// Perform a volatile read of each register, and store the values in the shadow buffer.
+    pub fn refresh(&mut self)
+    where
+        A: access::Readable,
+    {
+        for (reg, val) in self.arr.iter().zip(self.shadow.iter_mut()) {
+            *val = reg.read();
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use crate::arr::{ArrayElem, RegArray};
+use crate::bounds;
+use crate::iter::RegArrayIter;
+
+/// A dynamically-sized array of registers.
+///
+/// This is the counterpart of [`RegArray`] when the number of elements is only known at runtime,
+/// e.g. because it is read from a capability register of the device. A `RegSlice` is created
+/// with [`RegSlice::from_raw_parts`], or from a `RegArray` with [`RegArray::as_slice`].
+///
+/// Element types are the same as for [`RegArray`].
+pub struct RegSlice<'a, P: ArrayElem<'a>> {
+    ptr: NonNull<[P::Target]>,
+    _ref: PhantomData<&'a [P::Target]>,
+}
+impl<'a, P: ArrayElem<'a>> RegSlice<'a, P> {
+    /// Creates a new `RegSlice` of `len` elements starting at `base`.
+    ///
+    /// # Safety
+    /// - `base` must be non-null and properly aligned;
+    /// - `base` must point to `len` contiguous elements of type `P::Target`;
+    /// - the elements must be valid for the whole lifetime `'a`.
+    ///
+    /// In debug builds, panics if `base` is not properly aligned.
+    #[inline]
+    #[track_caller]
+    pub unsafe fn from_raw_parts(base: *mut P::Target, len: usize) -> Self {
+        debug_assert!(base.is_aligned(), "misaligned register slice pointer");
+        let ptr = core::ptr::slice_from_raw_parts_mut(base, len);
+        // SAFETY: the caller promises `base` is non-null
+        unsafe { Self::from_nonnull(NonNull::new_unchecked(ptr)) }
+    }
+    #[inline]
+    pub(crate) const unsafe fn from_nonnull(ptr: NonNull<[P::Target]>) -> Self {
+        Self {
+            ptr,
+            _ref: PhantomData,
+        }
+    }
+    /// Returns a raw pointer to the underlying pointer slice.
+    #[inline]
+    pub const fn as_ptr(&self) -> *mut [P::Target] {
+        self.ptr.as_ptr()
+    }
+    /// Returns the number of pointers in the slice.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.ptr.len()
+    }
+    /// Returns `true` if the slice has a length of 0.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Access the pointer at `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds, i.e. if `index >= self.len()`.
+    #[inline]
+    #[track_caller]
+    pub fn idx(&self, index: usize) -> P {
+        bounds::check_index_len(index, self.len());
+        // SAFETY: we checked i is in bounds
+        unsafe { self.idx_unchecked(index) }
+    }
+    /// Access the pointer at `index`, or returns `None` if `index` is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<P> {
+        // SAFETY: we checked i is in bounds
+        (index < self.len()).then(|| unsafe { self.idx_unchecked(index) })
+    }
+    /// Access the pointer at `index`, without doing bounds checking.
+    ///
+    /// # Safety
+    /// `index` must be in bounds: `index < self.len()`.
+    #[inline]
+    pub unsafe fn idx_unchecked(&self, index: usize) -> P {
+        let base: NonNull<P::Target> = self.ptr.cast();
+        // SAFETY: the caller promises we are in bounds
+        unsafe { P::from_nonnull(base.add(index)) }
+    }
+    /// Returns an iterator over the pointer slice.
+    #[inline]
+    pub fn iter(&self) -> RegArrayIter<'a, P> {
+        RegArrayIter::new(self.ptr)
+    }
+    /// Returns an iterator over a subslice `[start..end]` of the pointer slice.
+    ///
+    /// # Panics
+    /// If `[start..end]` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn iter_slice(&self, start: usize, end: usize) -> RegArrayIter<'a, P> {
+        bounds::check_slice_len(start, end, self.len());
+        let base: NonNull<P::Target> = self.ptr.cast();
+        // SAFETY: we checked that `[start..end]` is in bounds
+        let sub = unsafe { NonNull::slice_from_raw_parts(base.add(start), end - start) };
+        RegArrayIter::new(sub)
+    }
+}
+
+impl<'a, P: ArrayElem<'a>, const N: usize> From<RegArray<'a, P, N>> for RegSlice<'a, P> {
+    #[inline]
+    fn from(arr: RegArray<'a, P, N>) -> Self {
+        arr.as_slice()
+    }
+}
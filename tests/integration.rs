@@ -1,4 +1,7 @@
-use reg_map::RegMap;
+extern crate reg_map as renamed_reg_map;
+
+use reg_map::integers::RegValue;
+use reg_map::{RegMap, RegMapPtr};
 
 #[repr(C)]
 #[derive(RegMap, Default)]
@@ -7,6 +10,33 @@ pub struct Simple {
     field2: u64,
 }
 
+/// Fails to compile if the docs of the register map and of its fields are not replicated on the
+/// derived pointer, including on the accessors forwarded by `flatten`.
+#[deny(missing_docs)]
+pub mod documented {
+    use reg_map::RegMap;
+
+    /// A register map with documented fields.
+    #[repr(C)]
+    #[derive(RegMap, Default)]
+    pub struct Documented {
+        /// The control register.
+        pub control: u32,
+        /// The status registers.
+        #[reg(flatten)]
+        pub status: DocumentedStatus,
+    }
+
+    /// Documented registers to be flattened.
+    #[repr(C)]
+    #[derive(RegMap, Default)]
+    #[reg_map(flatten)]
+    pub struct DocumentedStatus {
+        /// The error flags.
+        pub errors: u32,
+    }
+}
+
 #[repr(C)]
 #[derive(RegMap)]
 struct Nested {
@@ -548,3 +578,3160 @@ fn array_4d_complex() {
         }
     }
 }
+
+#[test]
+fn read_each() {
+    let mut regs = Simple {
+        field1: 0xAA55,
+        field2: 0,
+    };
+    let ptr = SimplePtr::from_mut(&mut regs);
+
+    // other tests may have used the same addresses, only compare differences
+    #[cfg(feature = "profile")]
+    let (field1, field2) = (
+        ptr.field1().as_ptr() as usize,
+        ptr.field2().as_ptr() as usize,
+    );
+    #[cfg(feature = "profile")]
+    let (field1_reads, field2_reads) = (
+        reg_map::profile::counts(field1).0,
+        reg_map::profile::counts(field2).0,
+    );
+
+    let mut buf = [0u64; 8];
+    ptr.field1().read_each(&mut buf);
+    assert_eq!(buf, [0xAA55; 8]);
+
+    ptr.field2().read_each(&mut []);
+    #[cfg(feature = "profile")]
+    {
+        assert_eq!(
+            reg_map::profile::counts(field1).0,
+            field1_reads + buf.len() as u64
+        );
+        assert_eq!(reg_map::profile::counts(field2).0, field2_reads);
+    }
+    assert_eq!(regs.field1, 0xAA55);
+}
+
+#[test]
+fn reg_at() {
+    let mut regs = Simple {
+        field1: 1,
+        field2: 2,
+    };
+    let ptr = SimplePtr::from_mut(&mut regs);
+
+    let field2 = unsafe { ptr.reg_at::<u64>(8) };
+    assert_eq!(field2.as_ptr(), ptr.field2().as_ptr());
+    assert_eq!(field2.read(), 2);
+
+    field2.write(22);
+    assert_eq!(ptr.field2().read(), 22);
+
+    assert_eq!(regs.field1, 1);
+    assert_eq!(regs.field2, 22);
+}
+
+#[test]
+fn array_idx_wrapping() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let len = ptr.field2().len();
+
+    assert_eq!(
+        ptr.field2().idx_wrapping(len + 3).as_ptr(),
+        ptr.field2().idx(3).as_ptr()
+    );
+    assert_eq!(
+        ptr.field2().idx_wrapping(2 * len - 1).as_ptr(),
+        ptr.field2().idx(len - 1).as_ptr()
+    );
+
+    for i in 0..2 * len {
+        ptr.field2().idx_wrapping(i).write(i as u64);
+    }
+    for (i, v) in regs.field2.into_iter().enumerate() {
+        assert_eq!(v, (i + len) as u64);
+    }
+}
+
+#[cfg(feature = "compat")]
+#[test]
+fn compat_get_set() {
+    let mut regs = Simple::default();
+    let ptr = SimplePtr::from_mut(&mut regs);
+
+    ptr.field1().set(12);
+    assert_eq!(ptr.field1().get(), 12);
+    assert_eq!(ptr.field1().read(), 12);
+
+    ptr.field2().write(34);
+    assert_eq!(ptr.field2().get(), 34);
+
+    assert_eq!(regs.field1, 12);
+    assert_eq!(regs.field2, 34);
+}
+
+#[test]
+fn array_iter_slice_enumerated() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let mut it = ptr.field2().iter_slice_enumerated(5, 9).rev();
+    for (expected_rel, expected_abs) in [(3, 8), (2, 7), (1, 6), (0, 5)] {
+        let (rel, abs, elem) = it.next().unwrap();
+        assert_eq!(rel, expected_rel);
+        assert_eq!(abs, expected_abs);
+        assert_eq!(elem.as_ptr(), ptr.field2().idx(abs).as_ptr());
+        elem.write((rel * 100 + abs) as u64);
+    }
+    assert!(it.next().is_none());
+    drop(it);
+
+    for (i, v) in regs.field2.into_iter().enumerate() {
+        let expected = if (5..9).contains(&i) {
+            (i - 5) * 100 + i
+        } else {
+            0
+        };
+        assert_eq!(v, expected as u64);
+    }
+}
+
+#[test]
+fn array_iter_slice_addrs() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let items: Vec<_> = ptr.field2().iter_slice_addrs(5, 9).collect();
+    assert_eq!(items.len(), 4);
+    assert_eq!(items[0].0, ptr.field2().idx(5).as_ptr() as usize);
+    for (i, (addr, elem)) in items.iter().enumerate() {
+        assert_eq!(*addr, elem.as_ptr() as usize);
+        assert_eq!(*addr, items[0].0 + i * core::mem::size_of::<u64>());
+    }
+    assert_eq!(ptr.field2().iter_slice_addrs(3, 3).count(), 0);
+
+    let mut regs = Array4dComplex::default();
+    let ptr = Array4dComplexPtr::from_mut(&mut regs);
+    let (addr0, _) = ptr.data().iter_slice_addrs(1, 3).next().unwrap();
+    let (addr1, elem1) = ptr.data().iter_slice_addrs(1, 3).next_back().unwrap();
+    assert_eq!(addr1 - addr0, core::mem::size_of::<[[[Simple; 2]; 3]; 5]>());
+    assert_eq!(addr1, elem1.as_ptr() as usize);
+}
+
+#[test]
+fn update() {
+    let mut regs = Simple {
+        field1: 41,
+        field2: 0b1010,
+    };
+    let ptr = SimplePtr::from_mut(&mut regs);
+
+    assert_eq!(ptr.field1().update(|v| v + 1), 42);
+    assert_eq!(ptr.field1().read(), 42);
+    assert_eq!(ptr.field2().update(|v| v | 0b0101), 0b1111);
+    assert_eq!(ptr.field2().read(), 0b1111);
+
+    assert_eq!(regs.field1, 42);
+    assert_eq!(regs.field2, 0b1111);
+}
+
+#[test]
+fn fetch_update() {
+    let mut regs = Simple {
+        field1: 41,
+        field2: 7,
+    };
+    let ptr = SimplePtr::from_mut(&mut regs);
+
+    // update
+    assert_eq!(ptr.field1().fetch_update(|v| Some(v + 1)), Ok(41));
+    assert_eq!(ptr.field1().read(), 42);
+    // abort
+    assert_eq!(ptr.field2().fetch_update(|v| v.checked_sub(8)), Err(7));
+    assert_eq!(ptr.field2().read(), 7);
+    assert_eq!(ptr.field2().fetch_update(|v| v.checked_sub(7)), Ok(7));
+    assert_eq!(ptr.field2().read(), 0);
+
+    assert_eq!(regs.field1, 42);
+    assert_eq!(regs.field2, 0);
+
+    // registers without an atomic counterpart
+    let mut regs = MixedU {
+        one: 0,
+        two: 0,
+        four: 0,
+        eight: 0,
+        sixteen: 0,
+    };
+    let ptr = MixedUPtr::from_mut(&mut regs);
+    assert_eq!(ptr.sixteen().fetch_update(|v| Some(v | 1 << 100)), Ok(0));
+    assert_eq!(ptr.sixteen().fetch_update(|_| None), Err(1 << 100));
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct SmallArray {
+    data: [u32; 4],
+}
+
+#[test]
+fn array_pairwise() {
+    let mut regs = SmallArray {
+        data: [1, 4, 9, 16],
+    };
+    let ptr = SmallArrayPtr::from_mut(&mut regs);
+
+    let pairs = ptr.data().pairwise();
+    assert_eq!(pairs.len(), 3);
+    for (i, (a, b)) in pairs.enumerate() {
+        assert_eq!(a.as_ptr(), ptr.data().idx(i).as_ptr());
+        assert_eq!(b.as_ptr(), ptr.data().idx(i + 1).as_ptr());
+        assert_eq!(b.read() - a.read(), 2 * i as u32 + 3);
+    }
+
+    let (a, b) = ptr.data().pairwise().next_back().unwrap();
+    assert_eq!(a.as_ptr(), ptr.data().idx(2).as_ptr());
+    assert_eq!(b.as_ptr(), ptr.data().idx(3).as_ptr());
+}
+
+#[test]
+fn eq_addr() {
+    let mut regs = Array::default();
+    let base = core::ptr::addr_of_mut!(regs);
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let field1 = base.cast::<u64>();
+    let field2 = base.cast::<u8>().wrapping_add(8).cast::<[u64; 32]>();
+    assert!(ptr.field1().eq_addr(field1));
+    assert!(!ptr.field1().eq_addr(field2.cast()));
+    assert!(ptr.field2().eq_addr(field2));
+    assert!(ptr.field2().idx(0).eq_addr(field2.cast()));
+    assert!(ptr
+        .field2()
+        .idx(3)
+        .eq_addr(field2.cast::<u64>().wrapping_add(3)));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ipv4([u8; 4]);
+impl RegValue for Ipv4 {
+    type Bits = u32;
+    fn from_bits(bits: u32) -> Self {
+        Self(bits.to_be_bytes())
+    }
+    fn to_bits(self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct Network {
+    #[reg(RW, via = Ipv4)]
+    address: u32,
+    #[reg(via = Ipv4, RO)]
+    gateway: u32,
+    #[reg(via = Ipv4)]
+    dns: [u32; 2],
+    raw: u32,
+}
+
+#[test]
+fn via_custom_type() {
+    let mut regs = Network {
+        gateway: 0xC0A8_0001,
+        ..Default::default()
+    };
+    let ptr = NetworkPtr::from_mut(&mut regs);
+
+    assert_eq!(ptr.gateway().read(), Ipv4([192, 168, 0, 1]));
+
+    ptr.address().write(Ipv4([10, 0, 0, 42]));
+    assert_eq!(ptr.address().read(), Ipv4([10, 0, 0, 42]));
+
+    ptr.dns().idx(0).write(Ipv4([1, 1, 1, 1]));
+    ptr.dns().idx(1).write(Ipv4([8, 8, 4, 4]));
+    let dns: Vec<Ipv4> = ptr.dns().iter().map(|r| r.read()).collect();
+    assert_eq!(dns, [Ipv4([1, 1, 1, 1]), Ipv4([8, 8, 4, 4])]);
+
+    ptr.raw().write(7);
+    assert_eq!(ptr.raw().read(), 7);
+
+    assert_eq!(regs.address, 0x0A00_002A);
+    assert_eq!(regs.dns, [0x0101_0101, 0x0808_0404]);
+}
+
+#[test]
+fn array_copy_within() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let reset = || {
+        for (i, elem) in ptr.field2().iter().enumerate() {
+            elem.write(i as u64);
+        }
+    };
+
+    // backward move, overlapping
+    reset();
+    ptr.field2().copy_within(4..12, 2);
+    for (i, elem) in ptr.field2().iter().enumerate() {
+        let expected = if (2..10).contains(&i) { i + 2 } else { i };
+        assert_eq!(elem.read(), expected as u64);
+    }
+
+    // forward move, overlapping
+    reset();
+    ptr.field2().copy_within(4..12, 6);
+    for (i, elem) in ptr.field2().iter().enumerate() {
+        let expected = if (6..14).contains(&i) { i - 2 } else { i };
+        assert_eq!(elem.read(), expected as u64);
+    }
+
+    // move to the very end, not overlapping
+    reset();
+    ptr.field2().copy_within(0..2, 30);
+    assert_eq!(ptr.field2().idx(30).read(), 0);
+    assert_eq!(ptr.field2().idx(31).read(), 1);
+
+    // empty range
+    reset();
+    ptr.field2().copy_within(5..5, 32);
+    for (i, v) in regs.field2.into_iter().enumerate() {
+        assert_eq!(v, i as u64);
+    }
+}
+
+#[test]
+#[should_panic]
+fn array_copy_within_out_of_bounds() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    ptr.field2().copy_within(0..4, 30);
+}
+
+#[test]
+#[should_panic(expected = "out of range for slice of length 32")]
+fn array_copy_within_overflowing_dest() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    ptr.field2().copy_within(0..4, usize::MAX);
+}
+
+#[test]
+fn read_nonzero() {
+    use core::num::{NonZeroI8, NonZeroU128, NonZeroU64};
+
+    let mut regs = Simple {
+        field1: 0xC0FFEE,
+        field2: 0,
+    };
+    let ptr = SimplePtr::from_mut(&mut regs);
+    assert_eq!(ptr.field1().read_nonzero(), NonZeroU64::new(0xC0FFEE));
+    assert_eq!(ptr.field2().read_nonzero(), None);
+
+    let mut regs_u = MixedU {
+        one: 0,
+        two: 0,
+        four: 0,
+        eight: 0,
+        sixteen: u128::MAX,
+    };
+    let ptr_u = MixedUPtr::from_mut(&mut regs_u);
+    assert_eq!(ptr_u.one().read_nonzero(), None);
+    assert_eq!(ptr_u.sixteen().read_nonzero(), NonZeroU128::new(u128::MAX));
+
+    let mut regs_i = MixedI {
+        one: -1,
+        two: 0,
+        four: 0,
+        eight: 0,
+        sixteen: 0,
+    };
+    let ptr_i = MixedIPtr::from_mut(&mut regs_i);
+    assert_eq!(ptr_i.one().read_nonzero(), NonZeroI8::new(-1));
+    assert_eq!(ptr_i.sixteen().read_nonzero(), None);
+}
+
+#[repr(C, align(64))]
+#[derive(RegMap, Default)]
+struct Aligned {
+    field1: u32,
+}
+
+#[test]
+fn size_align() {
+    let mut regs = Simple::default();
+    let ptr = SimplePtr::from_mut(&mut regs);
+    assert_eq!(ptr.size(), core::mem::size_of::<Simple>());
+    assert_eq!(ptr.align(), core::mem::align_of::<Simple>());
+
+    let mut regs = MixedU {
+        one: 0,
+        two: 0,
+        four: 0,
+        eight: 0,
+        sixteen: 0,
+    };
+    let ptr = MixedUPtr::from_mut(&mut regs);
+    assert_eq!(ptr.size(), core::mem::size_of::<MixedU>());
+    assert_eq!(ptr.align(), core::mem::align_of::<MixedU>());
+
+    let mut regs = Aligned::default();
+    let ptr = AlignedPtr::from_mut(&mut regs);
+    assert_eq!(ptr.size(), 64);
+    assert_eq!(ptr.align(), 64);
+}
+
+#[test]
+fn array_iter_clone_reset() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    for (i, elem) in ptr.field2().iter().enumerate() {
+        elem.write(i as u64);
+    }
+
+    let mut it = ptr.field2().iter();
+    it.nth(3);
+    it.next_back();
+    let cloned = it.clone();
+    assert_eq!(cloned.len(), 27);
+    assert!(cloned
+        .zip(it.clone())
+        .all(|(a, b)| a.as_ptr() == b.as_ptr()));
+
+    it.reset();
+    assert_eq!(it.len(), 32);
+    for (i, elem) in it.by_ref().enumerate() {
+        assert_eq!(elem.read(), i as u64);
+    }
+    assert!(it.next().is_none());
+    it.reset();
+    assert_eq!(it.next().unwrap().read(), 0);
+    assert_eq!(it.next_back().unwrap().read(), 31);
+
+    let mut it = ptr.field2().iter_slice(10, 20);
+    it.by_ref().for_each(drop);
+    it.reset();
+    assert_eq!(it.len(), 10);
+    assert_eq!(it.next().unwrap().read(), 10);
+    assert_eq!(it.last().unwrap().read(), 19);
+}
+
+#[test]
+fn array_iter_len() {
+    let mut regs = Array4dComplex::default();
+    let ptr = Array4dComplexPtr::from_mut(&mut regs);
+
+    let mut it = ptr.data().iter();
+    assert_eq!(it.len(), 7);
+    for expected in (0..7).rev() {
+        it.next();
+        assert_eq!(it.len(), expected);
+    }
+    assert_eq!(it.len(), 0);
+
+    let mut it = ptr.data().idx(0).idx(0).iter_slice(1, 3);
+    assert_eq!(it.len(), 2);
+    it.next_back();
+    assert_eq!(it.len(), 1);
+    it.nth(5);
+    assert_eq!(it.len(), 0);
+    it.reset();
+    assert_eq!(it.len(), 2);
+}
+
+#[test]
+fn array_iter_rfold() {
+    let mut regs = Array::default();
+    for (i, x) in regs.field2.iter_mut().enumerate() {
+        *x = i as u64 + 1;
+    }
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    // each element is yielded, and read, exactly once, from the back
+    let mut reads = 0;
+    let mut next_addr = ptr.field2().idx(31).as_ptr();
+    let folded = ptr.field2().iter().rfold(0u64, |acc, r| {
+        assert_eq!(r.as_ptr(), next_addr);
+        next_addr = next_addr.wrapping_sub(1);
+        reads += 1;
+        acc * 3 % 1_000_003 + r.read()
+    });
+    assert_eq!(reads, 32);
+    let expected = (1..=32).rev().fold(0u64, |acc, x| acc * 3 % 1_000_003 + x);
+    assert_eq!(folded, expected);
+
+    // on a partially-consumed iterator
+    let mut it = ptr.field2().iter_slice(4, 12);
+    it.next();
+    it.next_back();
+    assert_eq!(it.rfold(0, |acc, _| acc + 1), 6);
+}
+
+#[test]
+fn write_bytes() {
+    let mut regs_u = MixedU {
+        one: 0,
+        two: 0,
+        four: 0,
+        eight: 0,
+        sixteen: 0,
+    };
+    let ptr_u = MixedUPtr::from_mut(&mut regs_u);
+    ptr_u.one().write_bytes(0xAA);
+    ptr_u.two().write_bytes(0xAA);
+    ptr_u.four().write_bytes(0xAA);
+    ptr_u.eight().write_bytes(0xAA);
+    ptr_u.sixteen().write_bytes(0xAA);
+    assert_eq!(ptr_u.one().read(), 0xAA);
+    assert_eq!(ptr_u.two().read(), 0xAAAA);
+    assert_eq!(ptr_u.four().read(), 0xAAAA_AAAA);
+    assert_eq!(ptr_u.eight().read(), 0xAAAA_AAAA_AAAA_AAAA);
+    assert_eq!(ptr_u.sixteen().read(), u128::MAX / 0xFF * 0xAA);
+
+    let mut regs_i = MixedI {
+        one: 0,
+        two: 0,
+        four: 0,
+        eight: 0,
+        sixteen: 0,
+    };
+    let ptr_i = MixedIPtr::from_mut(&mut regs_i);
+    ptr_i.four().write_bytes(0xFF);
+    ptr_i.sixteen().write_bytes(0x5A);
+    assert_eq!(ptr_i.four().read(), -1);
+    assert_eq!(ptr_i.sixteen().read(), (u128::MAX / 0xFF * 0x5A) as i128);
+    assert_eq!(ptr_i.eight().read(), 0);
+}
+
+#[test]
+fn array_debug_check_layout() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    assert!(ptr.field2().debug_check_layout());
+
+    let mut regs = SmallArray::default();
+    let ptr = SmallArrayPtr::from_mut(&mut regs);
+    assert!(ptr.data().debug_check_layout());
+
+    let mut regs = CAOuter::default();
+    let ptr = CAOuterPtr::from_mut(&mut regs);
+    assert!(ptr.outer().debug_check_layout());
+    assert!(ptr.outer().iter().all(|o| o.inner().debug_check_layout()));
+
+    let mut regs = Array4dComplex::default();
+    let ptr = Array4dComplexPtr::from_mut(&mut regs);
+    assert!(ptr.data().debug_check_layout());
+    assert!(ptr.data().idx(6).idx(4).idx(2).debug_check_layout());
+}
+
+#[cfg(feature = "use-atomics")]
+#[test]
+fn use_atomics_read_write() {
+    let mut regs_u = MixedU {
+        one: 1,
+        two: 2,
+        four: 4,
+        eight: 8,
+        sixteen: 16,
+    };
+    let ptr_u = MixedUPtr::from_mut(&mut regs_u);
+    assert_eq!(ptr_u.one().read(), 1);
+    assert_eq!(ptr_u.sixteen().read(), 16);
+    ptr_u.one().write(u8::MAX);
+    ptr_u.two().write(u16::MAX);
+    ptr_u.four().write(u32::MAX);
+    ptr_u.eight().write(u64::MAX);
+    ptr_u.sixteen().write(u128::MAX);
+    assert_eq!(ptr_u.four().update(|v| v - 1), u32::MAX - 1);
+
+    let mut regs_i = MixedI {
+        one: -1,
+        two: -2,
+        four: -4,
+        eight: -8,
+        sixteen: -16,
+    };
+    let ptr_i = MixedIPtr::from_mut(&mut regs_i);
+    assert_eq!(ptr_i.eight().read(), -8);
+    ptr_i.eight().write(i64::MIN);
+    ptr_i.sixteen().write(i128::MIN);
+
+    assert_eq!(regs_u.one, u8::MAX);
+    assert_eq!(regs_u.two, u16::MAX);
+    assert_eq!(regs_u.four, u32::MAX - 1);
+    assert_eq!(regs_u.eight, u64::MAX);
+    assert_eq!(regs_u.sixteen, u128::MAX);
+    assert_eq!(regs_i.eight, i64::MIN);
+    assert_eq!(regs_i.sixteen, i128::MIN);
+}
+
+#[test]
+fn array_iter_slice_clamped() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let arr = ptr.field2();
+
+    let mut it = arr.iter_slice_clamped(2, 5);
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.next().unwrap().as_ptr(), arr.idx(2).as_ptr());
+
+    // inverted
+    let mut it = arr.iter_slice_clamped(5, 2);
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.next().unwrap().as_ptr(), arr.idx(2).as_ptr());
+
+    // out of range
+    let mut it = arr.iter_slice_clamped(30, 100);
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.next().unwrap().as_ptr(), arr.idx(30).as_ptr());
+    assert_eq!(arr.iter_slice_clamped(100, 30).len(), 2);
+    assert_eq!(arr.iter_slice_clamped(0, usize::MAX).len(), 32);
+
+    // empty
+    assert_eq!(arr.iter_slice_clamped(40, 50).len(), 0);
+    assert_eq!(arr.iter_slice_clamped(7, 7).len(), 0);
+    assert!(arr.iter_slice_clamped(usize::MAX, 32).next().is_none());
+}
+
+#[test]
+fn dma_ptr() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    assert_eq!(ptr.field1().dma_ptr(), ptr.field1().as_ptr());
+    assert_eq!(ptr.field2().dma_ptr(), ptr.field2().as_ptr());
+    assert_eq!(ptr.field2().idx(5).dma_ptr(), ptr.field2().idx(5).as_ptr());
+
+    let src = [7u64; 4];
+    unsafe {
+        core::ptr::copy_nonoverlapping(
+            src.as_ptr(),
+            ptr.field2().dma_ptr().cast::<u64>().add(2),
+            src.len(),
+        )
+    };
+    assert_eq!(ptr.field2().idx(1).read(), 0);
+    assert_eq!(ptr.field2().idx(2).read(), 7);
+    assert_eq!(ptr.field2().idx(5).read(), 7);
+    assert_eq!(ptr.field2().idx(6).read(), 0);
+}
+
+#[repr(C)]
+#[derive(renamed_reg_map::RegMap, Default)]
+#[reg_map(crate = "renamed_reg_map")]
+struct Renamed {
+    #[reg(RO)]
+    field1: u32,
+    field2: [u16; 2],
+    inner: Simple,
+}
+
+#[test]
+fn renamed_crate() {
+    let mut regs = Renamed {
+        field1: 42,
+        ..Default::default()
+    };
+    let ptr = RenamedPtr::from_mut(&mut regs);
+
+    let _: renamed_reg_map::Reg<'_, u32, renamed_reg_map::access::ReadOnly> = ptr.field1();
+    assert_eq!(ptr.field1().read(), 42);
+    ptr.field2().idx(1).write(7);
+    ptr.inner().field1().write(3);
+    assert_eq!(regs.field2, [0, 7]);
+    assert_eq!(regs.inner.field1, 3);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(ptr_derive(Clone, Debug))]
+struct Derived {
+    #[reg(RO)]
+    id: u32,
+    data: u32,
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(crate = "reg_map", ptr_derive(Clone))]
+#[reg_map(ptr_derive(Copy))]
+struct DerivedCopy {
+    data: u32,
+}
+
+#[test]
+fn ptr_derive() {
+    let mut regs = Derived {
+        id: 7,
+        ..Default::default()
+    };
+    let ptr = DerivedPtr::from_mut(&mut regs);
+    let clone = ptr.clone();
+    clone.data().write(42);
+    assert_eq!(ptr.data().read(), 42);
+    assert!(format!("{:?}", ptr).starts_with("DerivedPtr"));
+    assert_eq!(regs.data, 42);
+
+    let mut regs = DerivedCopy::default();
+    let ptr = DerivedCopyPtr::from_mut(&mut regs);
+    let copy = ptr;
+    copy.data().write(3);
+    assert_eq!(ptr.data().read(), 3);
+}
+
+#[test]
+fn read_logged() {
+    let mut regs = Array {
+        field1: 42,
+        ..Default::default()
+    };
+    regs.field2[3] = 7;
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let (addr, val) = ptr.field1().read_logged();
+    assert_eq!(addr, ptr.field1().as_ptr() as usize);
+    assert_eq!(val, 42);
+
+    let (addr, val) = ptr.field2().idx(3).read_logged();
+    assert_eq!(addr, ptr.field2().idx(3).as_ptr() as usize);
+    assert_eq!(val, 7);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(fields)]
+struct Status {
+    flags: u32,
+    #[reg(RO)]
+    count: u32,
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct StatusArray {
+    #[reg(RO)]
+    items: [Status; 16],
+    #[reg(WO)]
+    commands: [Status; 2],
+    control: Status,
+}
+
+#[test]
+fn array_of_maps_access() {
+    use reg_map::access::{ReadOnly, ReadWrite, WriteOnly};
+
+    let mut regs = StatusArray::default();
+    regs.items[3].flags = 42;
+    regs.items[3].count = 7;
+    let ptr = StatusArrayPtr::from_mut(&mut regs);
+
+    let item: StatusPtr<'_, ReadOnly> = ptr.items().idx(3);
+    let _: reg_map::Reg<'_, u32, ReadOnly> = item.flags();
+    let _: reg_map::Reg<'_, u32, ReadOnly> = item.count();
+    assert_eq!(item.flags().read(), 42);
+    assert_eq!(item.count().read(), 7);
+    assert_eq!(
+        ptr.items().iter().map(|s| s.flags().read()).sum::<u32>(),
+        42
+    );
+
+    let command: StatusPtr<'_, WriteOnly> = ptr.commands().idx(1);
+    let _: reg_map::Reg<'_, u32, WriteOnly> = command.flags();
+    command.flags().write(5);
+
+    let control: StatusPtr<'_, ReadWrite> = ptr.control();
+    let _: StatusPtr<'_> = ptr.control();
+    let _: reg_map::Reg<'_, u32, ReadOnly> = control.count();
+    control.flags().write(3);
+
+    assert_eq!(regs.commands[1].flags, 5);
+    assert_eq!(regs.control.flags, 3);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct Thresholds {
+    #[reg(RO)]
+    table: [u32; 32],
+}
+
+#[test]
+fn array_partition_point() {
+    let mut regs = Thresholds::default();
+    for (i, x) in regs.table.iter_mut().enumerate() {
+        *x = 10 * i as u32;
+    }
+    let ptr = ThresholdsPtr::from_mut(&mut regs);
+    let table = ptr.table();
+
+    assert_eq!(table.partition_point(|_| false), 0);
+    assert_eq!(table.partition_point(|x| x < 5), 1);
+    assert_eq!(table.partition_point(|x| x < 10), 1);
+    assert_eq!(table.partition_point(|x| x <= 10), 2);
+    assert_eq!(table.partition_point(|x| x < 155), 16);
+    assert_eq!(table.partition_point(|x| x <= 310), 32);
+    assert_eq!(table.partition_point(|_| true), 32);
+
+    let reference: Vec<u32> = table.iter().map(|r| r.read()).collect();
+    for value in 0..330 {
+        assert_eq!(
+            table.partition_point(|x| x < value),
+            reference.partition_point(|&x| x < value)
+        );
+    }
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(fields)]
+struct Fields {
+    #[reg(RO)]
+    id: u16,
+    #[reg(WO)]
+    command: u8,
+    data: u32,
+    array: [u64; 2],
+    inner: Simple,
+    #[reg(RO, via = Ipv4)]
+    address: u32,
+}
+
+#[test]
+fn fields() {
+    use reg_map::access::AccessKind;
+    use reg_map::FieldInfo;
+
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+
+    let fields: Vec<FieldInfo> = ptr.fields().collect();
+    assert_eq!(
+        fields,
+        [
+            FieldInfo {
+                name: "id",
+                addr: ptr.id().as_ptr() as usize,
+                width: 16,
+                access: AccessKind::ReadOnly,
+            },
+            FieldInfo {
+                name: "command",
+                addr: ptr.command().as_ptr() as usize,
+                width: 8,
+                access: AccessKind::WriteOnly,
+            },
+            FieldInfo {
+                name: "data",
+                addr: ptr.data().as_ptr() as usize,
+                width: 32,
+                access: AccessKind::ReadWrite,
+            },
+            FieldInfo {
+                name: "address",
+                addr: ptr.address().as_ptr() as usize,
+                width: 32,
+                access: AccessKind::ReadOnly,
+            },
+        ]
+    );
+
+    // access propagated from the outer map
+    let mut regs = StatusArray::default();
+    let ptr = StatusArrayPtr::from_mut(&mut regs);
+    let control: Vec<FieldInfo> = ptr.control().fields().collect();
+    assert_eq!(control.len(), 2);
+    assert_eq!(control[1].name, "count");
+    assert_eq!(control[1].addr, ptr.control().count().as_ptr() as usize);
+    assert_eq!(control[1].width, 32);
+    let item: Vec<AccessKind> = ptr.items().idx(0).fields().map(|f| f.access).collect();
+    assert_eq!(item, [AccessKind::ReadOnly, AccessKind::ReadOnly]);
+    // the read-only `count` is not accessible through a write-only map
+    let command: Vec<&str> = ptr.commands().idx(0).fields().map(|f| f.name).collect();
+    assert_eq!(command, ["flags"]);
+
+    // non-zero, boolean and floating-point registers are listed too
+    let mut regs = FieldKinds {
+        enable: false,
+        gain: 0.0,
+        divider: core::num::NonZeroU16::MIN,
+        r#type: 0,
+    };
+    let ptr = FieldKindsPtr::from_mut(&mut regs);
+    let kinds: Vec<(&str, usize)> = ptr.fields().map(|f| (f.name, f.width)).collect();
+    assert_eq!(
+        kinds,
+        [("enable", 8), ("gain", 32), ("divider", 16), ("type", 8)]
+    );
+
+    // without `#[reg_map(fields)]`, a field can be named `fields`
+    let mut regs = FieldNamedFields::default();
+    FieldNamedFieldsPtr::from_mut(&mut regs).fields().write(3);
+    assert_eq!(regs.fields, 3);
+}
+
+#[repr(C)]
+#[derive(RegMap)]
+#[reg_map(fields)]
+struct FieldKinds {
+    enable: bool,
+    gain: f32,
+    divider: core::num::NonZeroU16,
+    r#type: u8,
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct FieldNamedFields {
+    fields: u32,
+}
+
+/// Returns whether `reg` is readable and writable.
+fn flags<T: RegValue, A: reg_map::access::Access>(_: &reg_map::Reg<'_, T, A>) -> (bool, bool) {
+    (
+        reg_map::Reg::<T, A>::is_readable(),
+        reg_map::Reg::<T, A>::is_writable(),
+    )
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct Grid {
+    #[reg(RO)]
+    status: [[u32; 4]; 4],
+    #[reg(WO)]
+    command: [[[u8; 2]; 2]; 2],
+}
+
+#[test]
+fn array_2d_access() {
+    let mut regs = Grid::default();
+    regs.status[2][3] = 23;
+    let ptr = GridPtr::from_mut(&mut regs);
+
+    for row in ptr.status().iter() {
+        for leaf in row.iter() {
+            assert_eq!(flags(&leaf), (true, false));
+        }
+    }
+    assert_eq!(ptr.status().at(&[2, 3]).read(), 23);
+    for leaf in ptr
+        .command()
+        .iter()
+        .flat_map(|a| a.iter())
+        .flat_map(|b| b.iter())
+    {
+        assert_eq!(flags(&leaf), (false, true));
+        leaf.write(1);
+    }
+    assert_eq!(regs.command, [[[1; 2]; 2]; 2]);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct StatusBanks {
+    #[reg(RO)]
+    banks: [[Status; 4]; 2],
+    #[reg(WO)]
+    commands: [[Group; 2]; 2],
+}
+
+#[test]
+fn array_2d_of_maps_access() {
+    use reg_map::access::{ReadOnly, WriteOnly};
+
+    let mut regs = StatusBanks::default();
+    regs.banks[1][3].flags = 13;
+    regs.banks[1][3].count = 31;
+    let ptr = StatusBanksPtr::from_mut(&mut regs);
+
+    let _: StatusPtr<'_, ReadOnly> = ptr.banks().idx(1).idx(3);
+    for bank in ptr.banks().iter() {
+        for status in bank.iter() {
+            assert_eq!(flags(&status.flags()), (true, false));
+            assert_eq!(flags(&status.count()), (true, false));
+        }
+    }
+    assert_eq!(ptr.banks().at(&[1, 3]).flags().read(), 13);
+    assert_eq!(ptr.banks().at(&[1, 3]).count().read(), 31);
+
+    let _: GroupPtr<'_, WriteOnly> = ptr.commands().idx(0).idx(1);
+    let group = ptr.commands().at(&[1, 0]);
+    assert_eq!(flags(&group.enable()), (false, true));
+    group.enable().write(1);
+    assert_eq!(regs.commands[1][0].enable, 1);
+}
+
+#[test]
+fn access_flags() {
+    use reg_map::access::{Access, ReadOnly, ReadWrite, WriteOnly};
+    use reg_map::Reg;
+
+    assert_eq!((ReadOnly::READABLE, ReadOnly::WRITABLE), (true, false));
+    assert_eq!((WriteOnly::READABLE, WriteOnly::WRITABLE), (false, true));
+    assert_eq!((ReadWrite::READABLE, ReadWrite::WRITABLE), (true, true));
+
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    assert_eq!(flags(&ptr.id()), (true, false));
+    assert_eq!(flags(&ptr.command()), (false, true));
+    assert_eq!(flags(&ptr.data()), (true, true));
+    // usable in const contexts
+    const READ_ONLY: (bool, bool) = (
+        Reg::<u32, ReadOnly>::is_readable(),
+        Reg::<u32, ReadOnly>::is_writable(),
+    );
+    assert_eq!(READ_ONLY, (true, false));
+}
+
+#[cfg(feature = "use-atomics")]
+#[test]
+fn compare_exchange() {
+    use core::sync::atomic::Ordering;
+
+    let mut regs = Fields {
+        data: 5,
+        ..Default::default()
+    };
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    let data = ptr.data();
+
+    assert_eq!(
+        data.compare_exchange(5, 10, Ordering::AcqRel, Ordering::Acquire),
+        Ok(5)
+    );
+    assert_eq!(data.read(), 10);
+    assert_eq!(
+        data.compare_exchange(5, 20, Ordering::Relaxed, Ordering::Relaxed),
+        Err(10)
+    );
+    assert_eq!(data.read(), 10);
+    assert_eq!(regs.data, 10);
+}
+
+#[cfg(feature = "use-atomics")]
+#[test]
+fn load_acquire_store_release() {
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    let data = ptr.data();
+
+    data.store_release(0xabcd);
+    assert_eq!(data.load_acquire(), 0xabcd);
+    assert_eq!(data.read(), 0xabcd);
+    data.write(7);
+    assert_eq!(data.load_acquire(), 7);
+    assert_eq!(regs.data, 7);
+}
+
+#[test]
+fn array_iter_unchecked() {
+    let mut regs = Array::default();
+    for (i, x) in regs.field2.iter_mut().enumerate() {
+        *x = 3 * i as u64;
+    }
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    assert_eq!(ptr.field2().iter_unchecked().size_hint(), (32, Some(32)));
+    assert!(ptr
+        .field2()
+        .iter_unchecked()
+        .zip(ptr.field2().iter())
+        .all(|(a, b)| a.as_ptr() == b.as_ptr() && a.read() == b.read()));
+    assert_eq!(ptr.field2().iter_unchecked().count(), 32);
+
+    let mut regs = Array4dComplex::default();
+    let ptr = Array4dComplexPtr::from_mut(&mut regs);
+    let mut it = ptr.data().idx(6).iter_unchecked();
+    for _ in 0..5 {
+        it.next().unwrap();
+    }
+    assert!(it.next().is_none());
+    assert!(it.next().is_none());
+}
+
+const ZERO: usize = 0;
+const MAYBE_ZERO: usize = if cfg!(feature = "compat") { 4 } else { 0 };
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct ZeroLength {
+    before: u32,
+    empty: [u64; ZERO],
+    maybe_empty: [u64; MAYBE_ZERO],
+    empty_maps: [Simple; ZERO],
+    after: u32,
+}
+
+#[test]
+fn array_zero_length() {
+    let mut regs = ZeroLength {
+        before: 1,
+        after: 2,
+        ..Default::default()
+    };
+    let ptr = ZeroLengthPtr::from_mut(&mut regs);
+
+    let empty = ptr.empty();
+    assert_eq!(empty.len(), 0);
+    assert!(empty.iter().next().is_none());
+    assert!(empty.iter().next_back().is_none());
+    assert!(empty.iter_unchecked().next().is_none());
+    assert!(empty.iter_slice(0, 0).next().is_none());
+    assert!(empty.iter_slice_clamped(3, 5).next().is_none());
+    assert!(empty.pairwise().next().is_none());
+    assert_eq!(empty.partition_point(|_| true), 0);
+    assert!(empty.debug_check_layout());
+    empty.copy_within(0..0, 0);
+    assert!(ptr.empty_maps().iter().next().is_none());
+    assert!(ptr.empty_maps().debug_check_layout());
+
+    let maybe_empty = ptr.maybe_empty();
+    assert_eq!(maybe_empty.len(), MAYBE_ZERO);
+    assert_eq!(maybe_empty.iter().count(), MAYBE_ZERO);
+    for reg in maybe_empty.iter() {
+        reg.write(5);
+    }
+    assert!(maybe_empty.iter().all(|reg| reg.read() == 5));
+    assert!(maybe_empty.debug_check_layout());
+
+    assert_eq!(ptr.before().read(), 1);
+    assert_eq!(ptr.after().read(), 2);
+}
+
+#[test]
+#[should_panic]
+fn array_zero_length_idx() {
+    let mut regs = ZeroLength::default();
+    let ptr = ZeroLengthPtr::from_mut(&mut regs);
+    ptr.empty().idx(0);
+}
+
+#[test]
+fn read_checked() {
+    let mut regs = MixedU {
+        one: u8::MAX,
+        two: 2,
+        four: u32::MAX,
+        eight: 0,
+        sixteen: 16,
+    };
+    let ptr = MixedUPtr::from_mut(&mut regs);
+
+    assert_eq!(ptr.one().read_checked(u8::MAX), Err(()));
+    assert_eq!(ptr.two().read_checked(u16::MAX), Ok(2));
+    assert_eq!(ptr.four().read_checked(u32::MAX), Err(()));
+    assert_eq!(ptr.eight().read_checked(u64::MAX), Ok(0));
+    assert_eq!(ptr.eight().read_checked(0), Err(()));
+    assert_eq!(ptr.sixteen().read_checked(u128::MAX), Ok(16));
+}
+
+#[test]
+fn read_defined() {
+    let mut regs = MixedU {
+        one: 0b1010_0101,
+        two: 0xffff,
+        four: 0xdead_beef,
+        eight: 0,
+        sixteen: 0,
+    };
+    let ptr = MixedUPtr::from_mut(&mut regs);
+
+    // the undefined bits are set, but masked out
+    assert_eq!(ptr.one().read_defined(0b0000_1111), 0b0000_0101);
+    assert_ne!(ptr.two().read(), 0x00ff);
+    assert_eq!(ptr.two().read_defined(0x00ff), 0x00ff);
+    assert_eq!(ptr.four().read_defined(0xffff_0000), 0xdead_0000);
+    assert_eq!(ptr.four().read_defined(0), 0);
+    assert_eq!(ptr.four().read_defined(u32::MAX), 0xdead_beef);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(flatten)]
+struct Group {
+    /// A field of the group.
+    enable: u32,
+    #[reg(RO)]
+    status: u32,
+    buffer: [u16; 4],
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct Flattened {
+    id: u32,
+    #[reg(flatten)]
+    group: Group,
+    #[reg(RO, flatten)]
+    status_group: StatusGroup,
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(flatten)]
+struct StatusGroup {
+    flags: u32,
+    count: u32,
+}
+
+#[test]
+fn flatten() {
+    use reg_map::access::{ReadOnly, ReadWrite};
+
+    let mut regs = Flattened::default();
+    regs.group.status = 3;
+    regs.status_group.count = 9;
+    let ptr = FlattenedPtr::from_mut(&mut regs);
+
+    let _: reg_map::Reg<'_, u32, ReadWrite> = ptr.enable();
+    let _: reg_map::Reg<'_, u32, ReadOnly> = ptr.status();
+    assert!(ptr.enable().eq_addr(ptr.group().enable().as_ptr()));
+    assert!(ptr.buffer().eq_addr(ptr.group().buffer().as_ptr()));
+    ptr.enable().write(1);
+    ptr.buffer().idx(2).write(5);
+    assert_eq!(ptr.status().read(), 3);
+
+    // access of the flattened map is propagated
+    let _: reg_map::Reg<'_, u32, ReadOnly> = ptr.flags();
+    assert_eq!(ptr.count().read(), 9);
+
+    // the nested accessor is still available
+    assert_eq!(ptr.group().enable().read(), 1);
+    assert_eq!(regs.group.enable, 1);
+    assert_eq!(regs.group.buffer, [0, 0, 5, 0]);
+}
+
+#[test]
+fn set_bits_iter() {
+    let mut regs = MixedU {
+        one: 0,
+        two: u16::MAX,
+        four: 0b1010_0000_0000_0000_0000_0000_1001_0001,
+        eight: 1 << 63,
+        sixteen: 1,
+    };
+    let ptr = MixedUPtr::from_mut(&mut regs);
+
+    assert_eq!(ptr.one().set_bits_iter().count(), 0);
+    assert!(ptr.two().set_bits_iter().eq(0..16));
+    assert_eq!(
+        ptr.four().set_bits_iter().collect::<Vec<_>>(),
+        [0, 4, 7, 29, 31]
+    );
+    assert_eq!(ptr.eight().set_bits_iter().collect::<Vec<_>>(), [63]);
+    assert_eq!(ptr.sixteen().set_bits_iter().collect::<Vec<_>>(), [0]);
+
+    // the register is read only once
+    let mut it = ptr.four().set_bits_iter();
+    assert_eq!(it.next(), Some(0));
+    ptr.four().write(0);
+    assert_eq!(it.collect::<Vec<_>>(), [4, 7, 29, 31]);
+}
+
+#[test]
+fn integer_bit_counts() {
+    use reg_map::integers::Integer;
+
+    fn check<T: Integer>(bits: u32) {
+        assert_eq!(T::ZERO.count_ones(), 0);
+        assert_eq!(T::ZERO.trailing_zeros(), bits);
+        assert_eq!(T::ZERO.leading_zeros(), bits);
+
+        assert_eq!(T::ONE.count_ones(), 1);
+        assert_eq!(T::ONE.trailing_zeros(), 0);
+        assert_eq!(T::ONE.leading_zeros(), bits - 1);
+
+        let all = !T::ZERO;
+        assert_eq!(all.count_ones(), bits);
+        assert_eq!(all.trailing_zeros(), 0);
+        assert_eq!(all.leading_zeros(), 0);
+
+        let msb = T::ONE << (bits - 1);
+        assert_eq!(msb.count_ones(), 1);
+        assert_eq!(msb.trailing_zeros(), bits - 1);
+        assert_eq!(msb.leading_zeros(), 0);
+
+        let mid = (T::ONE << 3) | (T::ONE << 5);
+        assert_eq!(mid.count_ones(), 2);
+        assert_eq!(mid.trailing_zeros(), 3);
+        assert_eq!(mid.leading_zeros(), bits - 6);
+    }
+
+    check::<u8>(8);
+    check::<u16>(16);
+    check::<u32>(32);
+    check::<u64>(64);
+    check::<u128>(128);
+    check::<i8>(8);
+    check::<i16>(16);
+    check::<i32>(32);
+    check::<i64>(64);
+    check::<i128>(128);
+}
+
+#[test]
+fn array_read_min_max() {
+    let mut regs = Array::default();
+    for (i, x) in regs.field2.iter_mut().enumerate() {
+        *x = (i as u64 * 7 + 3) % 32 + 100;
+    }
+    regs.field2[17] = 42;
+    regs.field2[5] = 1000;
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    assert_eq!(ptr.field2().read_min(), Some(42));
+    assert_eq!(ptr.field2().read_max(), Some(1000));
+
+    let mut regs = ZeroLength::default();
+    let ptr = ZeroLengthPtr::from_mut(&mut regs);
+    assert_eq!(ptr.empty().read_min(), None);
+    assert_eq!(ptr.empty().read_max(), None);
+}
+
+#[test]
+fn array_read_array() {
+    let mut regs = Array::default();
+    for (i, x) in regs.field2.iter_mut().enumerate() {
+        *x = i as u64 * 3;
+    }
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let vals: [u64; 32] = ptr.field2().read_array();
+    for (i, val) in vals.into_iter().enumerate() {
+        assert_eq!(val, ptr.field2().idx(i).read());
+    }
+
+    let mut regs = Array4d::default();
+    regs.data[6][4][2] = [5, 7];
+    let ptr = Array4dPtr::from_mut(&mut regs);
+    assert_eq!(ptr.data().idx(6).idx(4).idx(2).read_array(), [5, 7]);
+}
+
+#[test]
+fn array_write_array() {
+    let mut regs = SmallArray::default();
+    let ptr = SmallArrayPtr::from_mut(&mut regs);
+
+    ptr.data().write_array([4, 3, 2, 1]);
+    assert_eq!(ptr.data().read_array(), [4, 3, 2, 1]);
+    assert_eq!(regs.data, [4, 3, 2, 1]);
+}
+
+#[test]
+fn array_clear_unless() {
+    let mut regs = SmallArray {
+        data: [5, 12, 7, 30],
+    };
+    let ptr = SmallArrayPtr::from_mut(&mut regs);
+
+    ptr.data().clear_unless(|v| v >= 10);
+    assert_eq!(ptr.data().read_array(), [0, 12, 0, 30]);
+    ptr.data().clear_unless(|_| true);
+    assert_eq!(regs.data, [0, 12, 0, 30]);
+
+    let mut regs = SmallArray {
+        data: [5, 12, 7, 30],
+    };
+    let ptr = SmallArrayPtr::from_mut(&mut regs);
+    ptr.data().clear_unless(|_| false);
+    assert_eq!(regs.data, [0; 4]);
+}
+
+#[test]
+fn array_with_shadow() {
+    let mut regs = SmallArray { data: [1, 2, 3, 4] };
+    let ptr = SmallArrayPtr::from_mut(&mut regs);
+
+    let mut shadow = [0; 4];
+    let mut arr = ptr.data().with_shadow(&mut shadow);
+    assert_eq!(arr.shadow(), [0, 0, 0, 0]);
+    arr.refresh();
+    assert_eq!(arr.shadow(), [1, 2, 3, 4]);
+
+    arr.write(1, 20);
+    arr.write(3, 40);
+    assert_eq!(arr.read(1), 20);
+    assert_eq!(arr.shadow(), arr.array().read_array());
+
+    // out of sync when written elsewhere
+    ptr.data().idx(0).write(10);
+    assert_eq!(arr.read(0), 1);
+    arr.refresh();
+    assert_eq!(arr.read(0), 10);
+
+    assert_eq!(shadow, [10, 20, 3, 40]);
+    assert_eq!(regs.data, [10, 20, 3, 40]);
+}
+
+#[test]
+#[should_panic]
+fn array_with_shadow_wrong_len() {
+    let mut regs = SmallArray::default();
+    let ptr = SmallArrayPtr::from_mut(&mut regs);
+    let mut shadow = [0; 3];
+    ptr.data().with_shadow(&mut shadow);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn mock_reg() {
+    use reg_map::mock::MockReg;
+    use reg_map::RegisterAccess;
+
+    // driver logic under test
+    fn toggle_enable(ctrl: &impl RegisterAccess<u32>) {
+        ctrl.write(ctrl.read() ^ 1);
+    }
+
+    let mock = MockReg::new(0x10u32);
+    toggle_enable(&mock);
+    assert_eq!(mock.read(), 0x11);
+    mock.modify(|v| v << 1);
+    assert_eq!(mock.into_inner(), 0x22);
+
+    let mut regs = Fields {
+        data: 0x10,
+        ..Default::default()
+    };
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    toggle_enable(&ptr.data());
+    assert_eq!(regs.data, 0x11);
+}
+
+/// Driver logic generic over real and mock registers.
+fn set_mode(ctrl: &impl reg_map::RegisterAccess<u32>, mode: u32) -> bool {
+    ctrl.modify(|v| (v & !0b11) | (mode & 0b11));
+    ctrl.read() & 0b11 == mode
+}
+
+#[test]
+fn register_access() {
+    let mut regs = Fields {
+        data: 0xf0,
+        ..Default::default()
+    };
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    assert!(set_mode(&ptr.data(), 2));
+    assert!(!set_mode(&ptr.data(), 5));
+    assert_eq!(regs.data, 0xf1);
+
+    #[cfg(feature = "std")]
+    {
+        let mock = reg_map::mock::MockReg::new(0xf0u32);
+        assert!(set_mode(&mock, 2));
+        assert!(!set_mode(&mock, 5));
+        assert_eq!(mock.into_inner(), 0xf1);
+    }
+}
+
+#[test]
+fn array_iter_access() {
+    use reg_map::access::{ReadOnly, WriteOnly};
+    use reg_map::Reg;
+
+    let mut regs = Thresholds::default();
+    let ptr = ThresholdsPtr::from_mut(&mut regs);
+    let table = ptr.table();
+    let _: Option<Reg<'_, u32, ReadOnly>> = table.iter().next();
+    let _: Option<Reg<'_, u32, ReadOnly>> = table.iter().next_back();
+    let _: Option<Reg<'_, u32, ReadOnly>> = table.iter_slice(1, 3).next();
+    let _: Option<Reg<'_, u32, ReadOnly>> = table.iter_slice_clamped(1, 99).next();
+    let _: Option<Reg<'_, u32, ReadOnly>> = table.iter_unchecked().next();
+    let _: Option<(Reg<'_, u32, ReadOnly>, Reg<'_, u32, ReadOnly>)> = table.pairwise().next();
+    let _: Option<(usize, usize, Reg<'_, u32, ReadOnly>)> =
+        table.iter_slice_enumerated(1, 3).next();
+
+    let mut regs = StatusArray::default();
+    let ptr = StatusArrayPtr::from_mut(&mut regs);
+    for item in ptr.items().iter() {
+        let _: StatusPtr<'_, ReadOnly> = item;
+        let _: Reg<'_, u32, ReadOnly> = item.flags();
+    }
+    for command in ptr.commands().iter().rev() {
+        let _: StatusPtr<'_, WriteOnly> = command;
+        let _: Reg<'_, u32, WriteOnly> = command.flags();
+    }
+}
+
+#[test]
+fn from_unsafe_cell() {
+    use core::cell::UnsafeCell;
+
+    let cell = UnsafeCell::new(Simple {
+        field1: 1,
+        field2: 2,
+    });
+    let ptr = SimplePtr::from_unsafe_cell(&cell);
+    assert_eq!(ptr.field1().read(), 1);
+    ptr.field2().write(20);
+    assert_eq!(ptr.as_ptr(), cell.get());
+
+    // through the trait
+    let ptr2 = <SimplePtr as RegMapPtr>::from_unsafe_cell(&cell);
+    assert_eq!(ptr2.field2().read(), 20);
+    ptr2.field1().write(10);
+    assert_eq!(ptr.field1().read(), 10);
+
+    let regs = cell.into_inner();
+    assert_eq!(regs.field1, 10);
+    assert_eq!(regs.field2, 20);
+}
+
+#[test]
+fn array_at() {
+    let mut regs = Array4d::default();
+    let ptr = Array4dPtr::from_mut(&mut regs);
+
+    ptr.data().at(&[6, 4, 2, 1]).write(42);
+    assert_eq!(ptr.data().idx(6).idx(4).idx(2).idx(1).read(), 42);
+    assert_eq!(ptr.data().at(&[6, 4, 2, 1]).read(), 42);
+    assert_eq!(ptr.data().idx(3).at(&[4, 2, 0]).read(), 0);
+    assert_eq!(regs.data[6][4][2][1], 42);
+
+    let mut regs = Array4dComplex::default();
+    let ptr = Array4dComplexPtr::from_mut(&mut regs);
+    ptr.data().at(&[1, 2, 2, 0]).field2().write(7);
+    assert_eq!(regs.data[1][2][2][0].field2, 7);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(at_path)]
+struct Matrix {
+    #[reg(RO)]
+    cells: [[u64; 4]; 3],
+}
+
+#[test]
+fn at_path() {
+    let mut regs = Matrix::default();
+    regs.cells[2][1] = 21;
+    let ptr = MatrixPtr::from_mut(&mut regs);
+
+    let cell: reg_map::Reg<'_, u64, reg_map::access::ReadOnly> = ptr.at_path(&[2, 1]);
+    assert_eq!(cell.read(), 21);
+    assert_eq!(cell.as_ptr(), ptr.cells().idx(2).idx(1).as_ptr());
+    assert_eq!(ptr.at_path(&[0, 3]).read(), 0);
+
+    let mut regs = Tensor::default();
+    let ptr = TensorPtr::from_mut(&mut regs);
+    ptr.at_path(&[4, 3, 2, 1]).write(42);
+    assert_eq!(regs.data[4][3][2][1], 42);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(at_path, zeroed)]
+struct Tensor {
+    data: [[[[u8; 2]; 3]; 4]; 5],
+}
+
+#[test]
+#[should_panic]
+fn at_path_wrong_depth() {
+    let mut regs = Matrix::default();
+    let ptr = MatrixPtr::from_mut(&mut regs);
+    let _ = ptr.at_path(&[0]);
+}
+
+#[test]
+#[should_panic]
+fn array_at_too_few_indices() {
+    let mut regs = Array4d::default();
+    let ptr = Array4dPtr::from_mut(&mut regs);
+    let _ = ptr.data().at(&[0, 0, 0]);
+}
+
+#[test]
+#[should_panic]
+fn array_at_out_of_bounds() {
+    let mut regs = Array4d::default();
+    let ptr = Array4dPtr::from_mut(&mut regs);
+    let _ = ptr.data().at(&[0, 0, core::hint::black_box(3), 0]);
+}
+
+#[cfg(feature = "rayon")]
+#[repr(C)]
+#[derive(RegMap)]
+struct Large {
+    data: [u64; 100_000],
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn array_par_iter() {
+    use rayon::iter::ParallelIterator;
+
+    let mut regs = Box::new(Large { data: [0; 100_000] });
+    let ptr = LargePtr::from_mut(&mut regs);
+
+    let par = unsafe { ptr.data().par_iter() };
+    assert_eq!(par.len(), 100_000);
+    par.for_each(|r| r.write(r.as_ptr() as u64 & 0xff));
+    let expected = ptr.data().iter().map(|r| r.read()).sum::<u64>();
+    assert!(expected > 0);
+    let sum = unsafe { ptr.data().par_iter() }
+        .map(|r| r.read())
+        .sum::<u64>();
+    assert_eq!(sum, expected);
+
+    let mut regs = Array4dComplex::default();
+    let ptr = Array4dComplexPtr::from_mut(&mut regs);
+    unsafe { ptr.data().par_iter() }.for_each(|a| a.idx(0).idx(0).idx(0).field1().write(1));
+    assert_eq!(
+        ptr.data()
+            .iter()
+            .map(|a| a.at(&[0, 0, 0]).field1().read())
+            .sum::<u64>(),
+        7
+    );
+}
+
+/// Runs `f`, which must panic, and asserts the panic is reported at the caller's file and line.
+///
+/// The panic hook is installed once for the whole test binary and only records panics on
+/// threads that are inside this function, forwarding any other panic to the previous hook.
+#[track_caller]
+fn assert_panics_here<R>(f: impl FnOnce() -> R + std::panic::UnwindSafe) {
+    use std::cell::{Cell, RefCell};
+    use std::panic::{self, Location};
+    use std::sync::Once;
+
+    std::thread_local! {
+        static CAPTURING: Cell<bool> = const { Cell::new(false) };
+        static LOCATION: RefCell<Option<(String, u32)>> = const { RefCell::new(None) };
+    }
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let prev = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CAPTURING.get() {
+                let loc = info
+                    .location()
+                    .map(|loc| (loc.file().to_string(), loc.line()));
+                LOCATION.set(loc);
+            } else {
+                prev(info);
+            }
+        }));
+    });
+
+    CAPTURING.set(true);
+    let res = panic::catch_unwind(f);
+    CAPTURING.set(false);
+    assert!(res.is_err(), "expected a panic");
+    let caller = Location::caller();
+    assert_eq!(
+        LOCATION.take(),
+        Some((caller.file().to_string(), caller.line()))
+    );
+}
+
+#[test]
+fn array_panic_location() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let data = ptr.field2();
+    let len = core::hint::black_box(data.len());
+
+    assert_panics_here(|| data.idx(len));
+    assert_panics_here(|| data.iter_slice(2, len + 1));
+    assert_panics_here(|| data.copy_within(0..len, 1));
+
+    let mut regs = Array4d::default();
+    let ptr = Array4dPtr::from_mut(&mut regs);
+    let data = ptr.data();
+    assert_panics_here(|| data.at(&[0, 0, len, 0]));
+
+    let leaf = data.idx(0).idx(0).idx(0);
+    let mut shadow = [0; 3];
+    assert_panics_here(move || leaf.with_shadow(&mut shadow).array().len());
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(secure_clear)]
+struct Crypto {
+    #[reg(bytes)]
+    key: [u8; 16],
+    #[reg(RO, bytes)]
+    digest: [u8; 4],
+    plain: [u8; 4],
+}
+
+#[test]
+fn reg_bytes() {
+    let mut regs = Crypto {
+        digest: [1, 2, 3, 4],
+        ..Default::default()
+    };
+    let ptr = CryptoPtr::from_mut(&mut regs);
+
+    let _: reg_map::RegBytes<'_, 16, reg_map::access::ReadWrite> = ptr.key();
+    let _: reg_map::RegBytes<'_, 4, reg_map::access::ReadOnly> = ptr.digest();
+    let _: reg_map::RegArray<'_, reg_map::Reg<'_, u8, reg_map::access::ReadWrite>, 4> =
+        ptr.plain();
+
+    let key = *b"0123456789abcdef";
+    ptr.key().write(key);
+    assert_eq!(ptr.key().read(), key);
+    assert_eq!(ptr.key().len(), 16);
+    assert_eq!(ptr.key().as_ptr().cast(), ptr.as_ptr());
+    assert_eq!(ptr.digest().read(), [1, 2, 3, 4]);
+    assert_eq!(regs.key, key);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic = "misaligned register map pointer"]
+fn debug_misaligned_map() {
+    let mut buf = [0u64; 4];
+    let ptr = buf
+        .as_mut_ptr()
+        .cast::<u8>()
+        .wrapping_add(4)
+        .cast::<Simple>();
+    let _ = unsafe { <SimplePtr as RegMapPtr>::from_ptr(ptr) };
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic = "misaligned register pointer"]
+fn debug_misaligned_reg() {
+    let mut buf = [0u64; 4];
+    let ptr = buf
+        .as_mut_ptr()
+        .cast::<u8>()
+        .wrapping_add(4)
+        .cast::<Simple>();
+    // the const constructor cannot check the alignment, the field accessor does
+    let map = unsafe { SimplePtr::from_ptr(ptr) };
+    let _ = map.field1();
+}
+
+#[test]
+fn reg_slice_runtime_len() {
+    use reg_map::access::ReadWrite;
+    use reg_map::{Reg, RegSlice};
+
+    let mut buffer = vec![0u32; 16];
+    // pretend the length is discovered at runtime
+    let len = core::hint::black_box(10);
+    let slice =
+        unsafe { RegSlice::<Reg<u32, ReadWrite>>::from_raw_parts(buffer.as_mut_ptr(), len) };
+    assert_eq!(slice.len(), 10);
+    assert!(!slice.is_empty());
+    assert!(core::ptr::eq(slice.as_ptr().cast::<u32>(), buffer.as_ptr()));
+
+    for (i, reg) in slice.iter().enumerate() {
+        reg.write(i as u32 + 1);
+    }
+    for (i, reg) in slice.iter_slice(3, 7).enumerate() {
+        assert_eq!(reg.read(), i as u32 + 4);
+    }
+    assert_eq!(slice.idx(9).read(), 10);
+    assert!(slice.get(9).is_some());
+    assert!(slice.get(10).is_none());
+    assert_eq!(slice.iter().rev().map(|r| r.read()).next(), Some(10));
+    // the rest of the buffer is untouched
+    assert_eq!(buffer, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 0, 0, 0, 0, 0, 0]);
+
+    let empty = unsafe { RegSlice::<Reg<u32, ReadWrite>>::from_raw_parts(buffer.as_mut_ptr(), 0) };
+    assert!(empty.is_empty());
+    assert_eq!(empty.iter().count(), 0);
+}
+
+#[test]
+fn reg_slice_of_maps() {
+    use reg_map::RegSlice;
+
+    let mut buffer: Vec<SubArray> = (0..8).map(|_| SubArray::default()).collect();
+    let len = core::hint::black_box(buffer.len());
+    let slice = unsafe { RegSlice::<SubArrayPtr>::from_raw_parts(buffer.as_mut_ptr(), len) };
+    for (i, item) in slice.iter().enumerate() {
+        item.field1().write(i as u64 * 3);
+    }
+    assert_eq!(slice.idx(5).field1().read(), 15);
+    assert!(buffer
+        .iter()
+        .enumerate()
+        .all(|(i, s)| s.field1 == i as u64 * 3));
+}
+
+#[test]
+fn reg_slice_from_array() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let slice = ptr.field2().as_slice();
+    assert_eq!(slice.len(), 32);
+    assert!(core::ptr::eq(slice.as_ptr().cast(), ptr.field2().as_ptr()));
+    slice.idx(17).write(0xfeed);
+    assert_eq!(ptr.field2().idx(17).read(), 0xfeed);
+
+    let slice: reg_map::RegSlice<_> = ptr.field2().into();
+    assert_eq!(slice.iter().map(|r| r.read()).sum::<u64>(), 0xfeed);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn reg_slice_idx_out_of_bounds() {
+    use reg_map::access::ReadWrite;
+    use reg_map::{Reg, RegSlice};
+
+    let mut buffer = [0u16; 4];
+    let slice = unsafe { RegSlice::<Reg<u16, ReadWrite>>::from_raw_parts(buffer.as_mut_ptr(), 3) };
+    slice.idx(3);
+}
+
+#[test]
+#[should_panic]
+fn reg_slice_iter_slice_out_of_bounds() {
+    use reg_map::access::ReadWrite;
+    use reg_map::{Reg, RegSlice};
+
+    let mut buffer = [0u16; 4];
+    let slice = unsafe { RegSlice::<Reg<u16, ReadWrite>>::from_raw_parts(buffer.as_mut_ptr(), 3) };
+    slice.iter_slice(1, 4);
+}
+
+#[test]
+fn modify() {
+    let mut regs = Simple {
+        field1: 41,
+        field2: 0,
+    };
+    let ptr = SimplePtr::from_mut(&mut regs);
+
+    ptr.field1().modify(|v| v + 1);
+    assert_eq!(ptr.field1().read(), 42);
+    ptr.field1().modify(|v| v << 4);
+    assert_eq!(ptr.field1().read(), 42 << 4);
+    assert_eq!(ptr.field2().read(), 0);
+
+    // `modify` needs both permissions, see the compile_fail examples on `Reg::modify`
+    assert_eq!(flags(&ptr.field1()), (true, true));
+}
+
+#[test]
+fn read_twice_check() {
+    use core::cell::Cell;
+    use reg_map::RegisterAccess;
+
+    let mut regs = Simple {
+        field1: 0xabcd,
+        field2: 0,
+    };
+    let ptr = SimplePtr::from_mut(&mut regs);
+    assert_eq!(ptr.field1().read_twice_check(), Ok(0xabcd));
+
+    // a register on a glitchy bus, returning a different value on every read
+    struct Glitchy(Cell<u32>);
+    impl RegisterAccess<u32> for Glitchy {
+        fn read(&self) -> u32 {
+            let val = self.0.get();
+            self.0.set(val.wrapping_add(1));
+            val
+        }
+        fn write(&self, val: u32) {
+            self.0.set(val)
+        }
+    }
+    let glitchy = Glitchy(Cell::new(7));
+    assert_eq!(glitchy.read_twice_check(), Err((7, 8)));
+    assert_eq!(glitchy.read_twice_check(), Err((9, 10)));
+
+    #[cfg(feature = "std")]
+    {
+        let mock = reg_map::mock::MockReg::new(3u16);
+        assert_eq!(RegisterAccess::read_twice_check(&mock), Ok(3));
+    }
+}
+
+#[repr(C)]
+#[derive(RegMap)]
+struct NonZeroRegs {
+    divider: core::num::NonZeroU32,
+    #[reg(RO)]
+    id: core::num::NonZeroU16,
+    signed: core::num::NonZeroI8,
+    #[reg(WO)]
+    table: [[core::num::NonZeroU64; 3]; 2],
+}
+
+/// Same layout as `NonZeroRegs`, but allowing zero values in memory.
+#[repr(C)]
+#[derive(Default)]
+struct NonZeroRegsRaw {
+    divider: u32,
+    id: u16,
+    signed: i8,
+    table: [[u64; 3]; 2],
+}
+
+#[test]
+fn nonzero_fields() {
+    use core::num::{NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64};
+    use reg_map::access::{ReadOnly, ReadWrite, WriteOnly};
+
+    let mut raw = NonZeroRegsRaw {
+        divider: 4,
+        id: 0x1234,
+        ..Default::default()
+    };
+    let ptr = unsafe { NonZeroRegsPtr::from_ptr(core::ptr::addr_of_mut!(raw).cast()) };
+    let _: reg_map::RegNonZero<'_, u32, ReadWrite> = ptr.divider();
+    let _: reg_map::RegNonZero<'_, u16, ReadOnly> = ptr.id();
+    let _: reg_map::RegNonZero<'_, u64, WriteOnly> = ptr.table().idx(1).idx(2);
+
+    assert_eq!(ptr.divider().read(), NonZeroU32::new(4));
+    assert_eq!(ptr.id().read(), NonZeroU16::new(0x1234));
+    // the hardware erroneously returns zero
+    assert_eq!(ptr.signed().read(), None);
+
+    ptr.divider().write(NonZeroU32::new(16).unwrap());
+    ptr.signed().write(NonZeroI8::new(-3).unwrap());
+    ptr.table().idx(1).idx(2).write(NonZeroU64::MAX);
+    assert_eq!(ptr.divider().read(), NonZeroU32::new(16));
+    assert_eq!(ptr.signed().read(), NonZeroI8::new(-3));
+
+    assert_eq!(raw.divider, 16);
+    assert_eq!(raw.signed, -3);
+    assert_eq!(raw.table, [[0; 3], [0, 0, u64::MAX]]);
+}
+
+#[test]
+fn bit_helpers() {
+    macro_rules! check_bits {
+        ($($field:ident: $ty:ty),*) => {$(
+            let mut regs = MixedU {
+                one: 0,
+                two: 0,
+                four: 0,
+                eight: 0,
+                sixteen: 0,
+            };
+            let mut iregs = MixedI {
+                one: 0,
+                two: 0,
+                four: 0,
+                eight: 0,
+                sixteen: 0,
+            };
+            let uptr = MixedUPtr::from_mut(&mut regs);
+            let iptr = MixedIPtr::from_mut(&mut iregs);
+            let bits = <$ty as reg_map::integers::Integer>::BITS;
+            assert_eq!(bits as usize, 8 * core::mem::size_of::<$ty>());
+            let (u, i) = (uptr.$field(), iptr.$field());
+            u.set_bit(0);
+            u.set_bit(bits - 1);
+            i.set_bit(bits - 1);
+            assert!(u.get_bit(0) && u.get_bit(bits - 1) && !u.get_bit(1));
+            // the top bit is the sign bit
+            assert!(i.read() < 0);
+            u.toggle_bit(0);
+            u.toggle_bit(1);
+            assert!(!u.get_bit(0) && u.get_bit(1));
+            u.clear_bit(bits - 1);
+            i.clear_bit(bits - 1);
+            assert_eq!(u.read(), 0b10);
+            assert_eq!(i.read(), 0);
+        )*};
+    }
+    check_bits!(one: u8, two: u16, four: u32, eight: u64, sixteen: u128);
+}
+
+#[test]
+#[should_panic(expected = "bit index 32 out of range for 32 bits")]
+fn bit_helpers_out_of_range() {
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    ptr.data().set_bit(32);
+}
+
+#[test]
+fn bit_fields() {
+    let mut regs = Fields {
+        data: 0b1010_1010_1010_0101,
+        ..Default::default()
+    };
+    let ptr = FieldsPtr::from_mut(&mut regs);
+
+    // 3-bit field at bits 4..=6
+    assert_eq!(ptr.data().read_field(4, 3), 0b010);
+    ptr.data().write_field(4, 3, 0b101);
+    assert_eq!(ptr.data().read_field(4, 3), 0b101);
+    // the neighboring bits are untouched
+    assert_eq!(ptr.data().read(), 0b1010_1010_1101_0101);
+    // bits of the value above the width are ignored
+    ptr.data().write_field(4, 3, 0b1111_1000);
+    assert_eq!(ptr.data().read(), 0b1010_1010_1000_0101);
+
+    // full-width and empty fields
+    assert_eq!(ptr.data().read_field(0, 32), 0b1010_1010_1000_0101);
+    assert_eq!(ptr.data().read_field(32, 0), 0);
+    ptr.data().write_field(0, 32, u32::MAX);
+    assert_eq!(ptr.data().read_field(31, 1), 1);
+    ptr.data().write_field(32, 0, 0);
+    assert_eq!(regs.data, u32::MAX);
+
+    // sign extension does not leak into signed fields
+    let mut regs = MixedI {
+        one: 0,
+        two: 0,
+        four: -1,
+        eight: 0,
+        sixteen: 0,
+    };
+    let ptr = MixedIPtr::from_mut(&mut regs);
+    assert_eq!(ptr.four().read_field(28, 4), 0b1111);
+    ptr.four().write_field(28, 4, 0);
+    assert_eq!(ptr.four().read(), 0x0fff_ffff);
+}
+
+#[test]
+#[should_panic(expected = "bit field of width 3 at bit 30 out of range for 32 bits")]
+fn bit_fields_out_of_range() {
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    ptr.data().read_field(30, 3);
+}
+
+#[test]
+fn array_peekable_iter() {
+    use core::iter::Peekable;
+    use reg_map::access::ReadWrite;
+    use reg_map::{Reg, RegArrayIter};
+
+    /// A parser state storing the lookahead iterator.
+    struct Parser<'a> {
+        regs: Peekable<RegArrayIter<'a, Reg<'a, u64, ReadWrite>>>,
+    }
+
+    let mut regs = Array::default();
+    regs.field2[..4].copy_from_slice(&[1, 1, 2, 0]);
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let mut parser = Parser {
+        regs: ptr.field2().peekable_iter(),
+    };
+    // peeking does not consume
+    assert_eq!(parser.regs.peek().map(|r| r.read()), Some(1));
+    assert_eq!(parser.regs.peek().map(|r| r.read()), Some(1));
+    assert_eq!(parser.regs.len(), 32);
+
+    // group runs of equal values
+    let mut runs = Vec::new();
+    while let Some(first) = parser.regs.next() {
+        let val = first.read();
+        if val == 0 {
+            break;
+        }
+        let mut count = 1;
+        while parser.regs.next_if(|r| r.read() == val).is_some() {
+            count += 1;
+        }
+        runs.push((val, count));
+    }
+    assert_eq!(runs, [(1, 2), (2, 1)]);
+    assert_eq!(parser.regs.len(), 28);
+}
+
+#[test]
+fn write_from() {
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+
+    ptr.data().write_from(5u8);
+    assert_eq!(ptr.data().read(), 5);
+    ptr.data().write_from(0xbeefu16);
+    assert_eq!(ptr.data().read(), 0xbeef);
+    ptr.data().write_from(true);
+    assert_eq!(ptr.data().read(), 1);
+    ptr.array().idx(1).write_from(u32::MAX);
+    assert_eq!(regs.array, [0, u32::MAX as u64]);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(fields)]
+struct ReadClearRegs {
+    #[reg(RC)]
+    status: u32,
+    #[reg(RC)]
+    errors: [u16; 2],
+    #[reg(RO)]
+    nested: ReadClearInner,
+    #[reg(RC)]
+    cleared: ReadClearInner,
+}
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct ReadClearInner {
+    #[reg(RC)]
+    flags: u32,
+    id: u32,
+}
+
+#[test]
+fn read_clear_access() {
+    use reg_map::access::{AccessKind, ReadClear, ReadOnly};
+
+    let mut regs = ReadClearRegs {
+        status: 0x81,
+        ..Default::default()
+    };
+    let ptr = ReadClearRegsPtr::from_mut(&mut regs);
+    let _: reg_map::Reg<'_, u32, ReadClear> = ptr.status();
+    let _: reg_map::Reg<'_, u16, ReadClear> = ptr.errors().idx(1);
+    // read-to-clear fields stay read-to-clear in read-only maps
+    let _: reg_map::Reg<'_, u32, ReadClear> = ptr.nested().flags();
+    let _: reg_map::Reg<'_, u32, ReadOnly> = ptr.nested().id();
+    // read-to-clear maps make all their fields read-to-clear
+    let _: reg_map::Reg<'_, u32, ReadClear> = ptr.cleared().id();
+
+    // reads are plain volatile reads, the memory backing the test is not cleared
+    assert_eq!(ptr.status().read(), 0x81);
+    assert!(ptr.status().get_bit(7));
+    assert_eq!(flags(&ptr.status()), (true, false));
+
+    let kinds: Vec<_> = ptr.fields().map(|f| (f.name, f.access)).collect();
+    assert_eq!(kinds, [("status", AccessKind::ReadClear)]);
+    assert_eq!(
+        AccessKind::ReadOnly.restrict(AccessKind::ReadClear),
+        Some(AccessKind::ReadClear)
+    );
+    assert_eq!(AccessKind::WriteOnly.restrict(AccessKind::ReadClear), None);
+    assert!(AccessKind::ReadClear.is_readable() && !AccessKind::ReadClear.is_writable());
+}
+
+/// A header followed by a flexible array member, as in `struct packet { u32 len; u32 flags;
+/// u16 data[]; }` in C.
+#[repr(C)]
+#[derive(RegMap)]
+#[reg_map(tail)]
+struct Packet {
+    #[reg(RO)]
+    len: u32,
+    flags: u32,
+    #[reg(WO)]
+    data: [u16; 0],
+}
+
+#[repr(C)]
+#[derive(RegMap)]
+#[reg_map(tail)]
+struct Descriptors {
+    count: u64,
+    entries: [SubArray; 0],
+}
+
+#[test]
+fn flexible_array_tail() {
+    use reg_map::access::WriteOnly;
+    use reg_map::{Reg, RegSlice};
+
+    assert_eq!(core::mem::size_of::<Packet>(), 8);
+    let mut buffer = [0u32; 6];
+    buffer[0] = 7;
+    let ptr = unsafe { PacketPtr::from_ptr(buffer.as_mut_ptr().cast()) };
+    let len = ptr.len().read() as usize;
+    let tail: RegSlice<'_, Reg<'_, u16, WriteOnly>> = unsafe { ptr.tail(len) };
+    assert_eq!(tail.len(), 7);
+    assert!(core::ptr::eq(
+        tail.as_ptr().cast::<u32>(),
+        buffer[2..].as_ptr()
+    ));
+    for (i, reg) in tail.iter().enumerate() {
+        reg.write(i as u16 + 1);
+    }
+    ptr.flags().write(1);
+    assert_eq!(buffer[1], 1);
+    let halves: Vec<u16> = buffer[2..]
+        .iter()
+        .flat_map(|word| {
+            let [a, b, c, d] = word.to_ne_bytes();
+            [u16::from_ne_bytes([a, b]), u16::from_ne_bytes([c, d])]
+        })
+        .collect();
+    assert_eq!(halves, [1, 2, 3, 4, 5, 6, 7, 0]);
+
+    let mut buffer = [0u64; 4];
+    buffer[0] = 3;
+    let ptr = unsafe { DescriptorsPtr::from_ptr(buffer.as_mut_ptr().cast()) };
+    let entries = unsafe { ptr.tail(ptr.count().read() as usize) };
+    entries.idx(2).field1().write(0xab);
+    assert_eq!(buffer, [3, 0, 0, 0xab]);
+
+    // the length of the trailing array can be any constant expression evaluating to zero
+    let mut buffer = [1u8, 2, 3];
+    let ptr = unsafe { NamedTailPtr::from_ptr(buffer.as_mut_ptr().cast()) };
+    assert_eq!(unsafe { ptr.tail(2) }.idx(1).read(), 3);
+}
+
+const NO_ENTRIES: usize = 0;
+
+#[repr(C)]
+#[derive(RegMap)]
+#[reg_map(tail)]
+struct NamedTail {
+    kind: u8,
+    data: [u8; NO_ENTRIES],
+}
+
+/// Without the opt-in attributes, fields can have the same names as the optional methods.
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct OptionalMethodNames {
+    secure_clear: u32,
+    at_path: u16,
+    tail: [u16; 0],
+}
+
+#[test]
+fn optional_method_names() {
+    let mut regs = OptionalMethodNames::default();
+    let ptr = OptionalMethodNamesPtr::from_mut(&mut regs);
+    ptr.secure_clear().write(1);
+    ptr.at_path().write(2);
+    assert_eq!(ptr.tail().len(), 0);
+    assert_eq!((regs.secure_clear, regs.at_path), (1, 2));
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(fields)]
+struct InterruptRegs {
+    #[reg(W1C)]
+    pending: u32,
+    enable: u32,
+    #[reg(RO)]
+    banks: [InterruptBank; 2],
+}
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct InterruptBank {
+    #[reg(W1C)]
+    pending: u8,
+}
+
+#[test]
+fn write_one_to_clear() {
+    use reg_map::access::{AccessKind, ReadOnly, Write1Clear};
+
+    let mut regs = InterruptRegs {
+        pending: 0b1111,
+        ..Default::default()
+    };
+    let ptr = InterruptRegsPtr::from_mut(&mut regs);
+    let _: reg_map::Reg<'_, u32, Write1Clear> = ptr.pending();
+    // write-1-to-clear fields are read-only in read-only maps
+    let _: reg_map::Reg<'_, u8, ReadOnly> = ptr.banks().idx(1).pending();
+    assert_eq!(flags(&ptr.pending()), (true, true));
+
+    assert_eq!(ptr.pending().read(), 0b1111);
+    // the memory backing the test just records the single write of the mask
+    ptr.pending().clear_flags(1 << 2);
+    assert_eq!(regs.pending, 0b100);
+
+    let kinds: Vec<_> = InterruptRegsPtr::from_mut(&mut regs)
+        .fields()
+        .map(|f| f.access)
+        .collect();
+    assert_eq!(kinds, [AccessKind::Write1Clear, AccessKind::ReadWrite]);
+    assert_eq!(
+        AccessKind::ReadOnly.restrict(AccessKind::Write1Clear),
+        Some(AccessKind::ReadOnly)
+    );
+    assert_eq!(
+        AccessKind::Write1Clear.restrict(AccessKind::ReadWrite),
+        Some(AccessKind::Write1Clear)
+    );
+}
+
+#[test]
+fn is_zero() {
+    let mut regs = MixedU {
+        one: 0,
+        two: 0,
+        four: 0,
+        eight: 0,
+        sixteen: 0,
+    };
+    let ptr = MixedUPtr::from_mut(&mut regs);
+    assert!(ptr.one().is_zero() && ptr.two().is_zero() && ptr.four().is_zero());
+    assert!(ptr.eight().is_zero() && ptr.sixteen().is_zero());
+    ptr.one().write(1);
+    ptr.four().write(1 << 31);
+    ptr.sixteen().write(1 << 127);
+    assert!(!ptr.one().is_zero() && !ptr.four().is_zero() && !ptr.sixteen().is_zero());
+    assert!(ptr.two().is_zero() && ptr.eight().is_zero());
+
+    let mut regs = MixedI {
+        one: -1,
+        two: i16::MIN,
+        four: 0,
+        eight: 0,
+        sixteen: -1,
+    };
+    let ptr = MixedIPtr::from_mut(&mut regs);
+    assert!(!ptr.one().is_zero() && !ptr.two().is_zero() && !ptr.sixteen().is_zero());
+    assert!(ptr.four().is_zero() && ptr.eight().is_zero());
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct RawNames {
+    r#type: u32,
+    r#match: [u16; 2],
+}
+
+#[test]
+fn offset_constants() {
+    assert_eq!(SimplePtr::FIELD1_OFFSET, 0);
+    assert_eq!(SimplePtr::FIELD2_OFFSET, 8);
+    // padding is accounted for
+    assert_eq!(MixedReversePtr::SIXTEEN_OFFSET, 0);
+    assert_eq!(MixedReversePtr::ONE_OFFSET, 30);
+    assert_eq!(FieldsPtr::COMMAND_OFFSET, 2);
+    assert_eq!(FieldsPtr::DATA_OFFSET, 4);
+    assert_eq!(FieldsPtr::ARRAY_OFFSET, 8);
+    // raw identifiers are named without the `r#` prefix
+    assert_eq!(RawNamesPtr::TYPE_OFFSET, 0);
+    assert_eq!(RawNamesPtr::MATCH_OFFSET, 4);
+    let mut regs = RawNames::default();
+    RawNamesPtr::from_mut(&mut regs).r#match().idx(1).write(7);
+    assert_eq!(regs.r#match, [0, 7]);
+
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let base = ptr.as_ptr() as usize;
+    assert_eq!(
+        ptr.field2().as_ptr() as usize - base,
+        ArrayPtr::FIELD2_OFFSET
+    );
+}
+
+#[test]
+fn field_addr() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let addr = ptr.field_addr(ArrayPtr::FIELD2_OFFSET);
+    assert_eq!(addr, ptr.field2().as_ptr().cast());
+
+    let mut regs = CAOuter::default();
+    let base = core::ptr::addr_of_mut!(regs);
+    let ptr = CAOuterPtr::from_mut(&mut regs);
+    let inner = ptr.outer().idx(3);
+    assert_eq!(
+        inner.field_addr(CAInnerPtr::INNER_OFFSET),
+        unsafe { core::ptr::addr_of_mut!((*base).outer[3].inner) }.cast()
+    );
+}
+
+#[test]
+fn array_get() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    for (i, reg) in ptr.field2().iter().enumerate() {
+        reg.write(i as u64 * 10);
+    }
+
+    assert_eq!(ptr.field2().get(0).map(|r| r.read()), Some(0));
+    assert_eq!(ptr.field2().get(31).map(|r| r.read()), Some(310));
+    assert!(ptr.field2().get(32).is_none());
+    assert!(ptr.field2().get(usize::MAX).is_none());
+    assert_eq!(unsafe { ptr.field2().get_unchecked(7) }.read(), 70);
+
+    let mut regs = CAOuter::default();
+    let ptr = CAOuterPtr::from_mut(&mut regs);
+    assert!(ptr.outer().get(6).and_then(|o| o.inner().get(10)).is_some());
+    assert!(ptr.outer().get(6).and_then(|o| o.inner().get(11)).is_none());
+    assert!(ptr.outer().get(7).is_none());
+}
+
+#[cfg(feature = "profile")]
+#[test]
+fn profile_counts() {
+    use reg_map::profile;
+
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    let data = ptr.data().as_ptr() as usize;
+    let array = ptr.array().idx(1).as_ptr() as usize;
+    // other tests may have used the same addresses, only compare differences
+    let (data_reads, data_writes) = profile::counts(data);
+    let (array_reads, array_writes) = profile::counts(array);
+
+    ptr.data().write(1);
+    ptr.data().read();
+    ptr.data().modify(|v| v + 1);
+    ptr.data().set_bit(4);
+    let _ = ptr.data().fetch_update(|_| None);
+    ptr.array().idx(1).write(3);
+    for reg in ptr.array().iter() {
+        reg.read();
+    }
+
+    assert_eq!(profile::counts(data), (data_reads + 4, data_writes + 3));
+    assert_eq!(profile::counts(array), (array_reads + 1, array_writes + 1));
+}
+
+#[test]
+fn array_first_last() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    for (i, reg) in ptr.field2().iter().enumerate() {
+        reg.write(i as u64 + 100);
+    }
+    assert_eq!(ptr.field2().first().read(), 100);
+    assert_eq!(ptr.field2().last().read(), 131);
+    assert_eq!(ptr.field2().get_first().map(|r| r.read()), Some(100));
+    assert_eq!(ptr.field2().get_last().map(|r| r.read()), Some(131));
+    assert!(core::ptr::eq(
+        ptr.field2().last().as_ptr(),
+        ptr.field2().idx(31).as_ptr()
+    ));
+
+    let mut regs = ZeroLength::default();
+    let ptr = ZeroLengthPtr::from_mut(&mut regs);
+    assert!(ptr.empty().get_first().is_none());
+    assert!(ptr.empty().get_last().is_none());
+    assert!(ptr.empty_maps().get_last().is_none());
+}
+
+#[test]
+#[should_panic(expected = "last element of empty array")]
+fn array_last_empty() {
+    let mut regs = ZeroLength::default();
+    let ptr = ZeroLengthPtr::from_mut(&mut regs);
+    ptr.empty().last();
+}
+
+#[repr(C)]
+#[derive(RegMap)]
+#[reg_map(zeroed)]
+struct ZeroedMixed {
+    one: u8,
+    sixteen: u128,
+    table: [u64; 32],
+}
+
+#[test]
+fn zeroed_const() {
+    static mut BACKING: Tensor = Tensor::ZEROED;
+    const ZEROED_MIXED: ZeroedMixed = ZeroedMixed::ZEROED;
+
+    assert_eq!((ZEROED_MIXED.one, ZEROED_MIXED.sixteen), (0, 0));
+    assert_eq!(ZEROED_MIXED.table, [0; 32]);
+    let ptr = unsafe { TensorPtr::from_ptr(core::ptr::addr_of_mut!(BACKING)) };
+    assert!(ptr.data().iter().all(|d| d.idx(3).idx(2).idx(1).is_zero()));
+    ptr.at_path(&[4, 3, 2, 1]).write(9);
+    assert_eq!(ptr.data().idx(4).idx(3).idx(2).idx(1).read(), 9);
+
+    // without `#[reg_map(zeroed)]`, the map can define its own `ZEROED`
+    assert_eq!(OwnZeroed::ZEROED.count, 1);
+}
+
+#[repr(C)]
+#[derive(RegMap)]
+struct OwnZeroed {
+    count: u32,
+}
+impl OwnZeroed {
+    const ZEROED: Self = Self { count: 1 };
+}
+
+#[test]
+fn array_read_iter_map() {
+    let mut regs = Array::default();
+    // unsigned Q16.16 fixed-point values
+    regs.field2[0] = 1 << 16;
+    regs.field2[1] = 3 << 15;
+    regs.field2[2] = 1 << 14;
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let to_f64 = |raw: u64| raw as f64 / 65536.0;
+    let mut decoded = ptr.field2().read_iter_map(to_f64);
+    assert_eq!(decoded.len(), 32);
+    assert_eq!(decoded.next(), Some(1.0));
+    assert_eq!(decoded.next(), Some(1.5));
+    assert_eq!(decoded.next(), Some(0.25));
+    assert_eq!(decoded.next_back(), Some(0.0));
+    assert_eq!(decoded.len(), 28);
+
+    // the elements are read lazily
+    let mut decoded = ptr.field2().read_iter_map(to_f64).skip(3);
+    ptr.field2().idx(3).write(5 << 16);
+    assert_eq!(decoded.next(), Some(5.0));
+}
+
+#[test]
+fn array_read_write_all() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let src: [u64; 32] = core::array::from_fn(|i| (i as u64 + 1) * 0x0101);
+    ptr.field2().write_all(&src);
+    let mut out = [0u64; 32];
+    ptr.field2().read_all(&mut out);
+    assert_eq!(out, src);
+    assert_eq!(regs.field2, src);
+}
+
+#[test]
+#[should_panic(expected = "buffer length must match the array length")]
+fn array_read_all_wrong_len() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let mut out = [0u64; 31];
+    ptr.field2().read_all(&mut out);
+}
+
+#[test]
+#[should_panic(expected = "buffer length must match the array length")]
+fn array_write_all_wrong_len() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    ptr.field2().write_all(&[0u64; 33]);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct Scratch {
+    words: [u32; 16],
+}
+
+#[test]
+fn array_fill() {
+    let mut regs = Scratch::default();
+    let ptr = ScratchPtr::from_mut(&mut regs);
+
+    ptr.words().fill(0xDEAD_BEEF);
+    assert_eq!(regs.words, [0xDEAD_BEEF; 16]);
+
+    let ptr = ScratchPtr::from_mut(&mut regs);
+    ptr.words().fill_with(|i| i as u32 * 4);
+    let expected: [u32; 16] = core::array::from_fn(|i| i as u32 * 4);
+    assert_eq!(regs.words, expected);
+}
+
+#[cfg(feature = "use-atomics")]
+#[test]
+fn atomic_fetch_bitwise() {
+    use core::sync::atomic::Ordering;
+
+    let mut regs = Fields {
+        data: 0b1010,
+        ..Default::default()
+    };
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    let data = ptr.data();
+
+    assert_eq!(data.fetch_or(0b0101, Ordering::AcqRel), 0b1010);
+    assert_eq!(data.read(), 0b1111);
+    assert_eq!(data.fetch_and(0b0110, Ordering::Relaxed), 0b1111);
+    assert_eq!(data.read(), 0b0110);
+    assert_eq!(data.fetch_xor(0b0011, Ordering::SeqCst), 0b0110);
+    assert_eq!(data.read(), 0b0101);
+    assert_eq!(regs.data, 0b0101);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(secure_clear)]
+struct KeyStore {
+    #[reg(RO)]
+    status: u32,
+    key: [u32; 4],
+    #[reg(WO)]
+    iv: KeyStoreIv,
+    #[reg(RC)]
+    errors: u32,
+}
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(secure_clear)]
+struct KeyStoreIv {
+    words: [[u16; 2]; 2],
+    #[reg(RO)]
+    valid: u32,
+}
+
+#[repr(C)]
+#[derive(RegMap)]
+#[reg_map(secure_clear)]
+struct ClearNonZero {
+    divider: core::num::NonZeroU32,
+    data: u32,
+    table: [core::num::NonZeroU16; 2],
+}
+
+#[repr(C)]
+#[derive(RegMap)]
+#[reg_map(secure_clear)]
+struct ClearFields {
+    #[reg(RO)]
+    id: u16,
+    #[reg(WO)]
+    command: u8,
+    data: u32,
+    array: [u64; 2],
+    inner: KeyStoreIv,
+    #[reg(RO, via = Ipv4)]
+    address: u32,
+}
+
+#[test]
+fn secure_clear() {
+    let mut regs = ClearFields {
+        id: 0x1234,
+        command: 0x56,
+        data: 0xdead_beef,
+        array: [1, 2],
+        inner: KeyStoreIv {
+            words: [[3, 4], [5, 6]],
+            valid: 1,
+        },
+        address: 0x0a00_0001,
+    };
+    ClearFieldsPtr::from_mut(&mut regs).secure_clear();
+    assert_eq!(regs.id, 0x1234);
+    assert_eq!(regs.command, 0);
+    assert_eq!(regs.data, 0);
+    assert_eq!(regs.array, [0, 0]);
+    assert_eq!((regs.inner.words, regs.inner.valid), ([[0; 2]; 2], 1));
+    assert_eq!(regs.address, 0x0a00_0001);
+
+    let mut regs = KeyStore {
+        status: 1,
+        key: [0xaaaa_5555; 4],
+        iv: KeyStoreIv {
+            words: [[0xffff; 2]; 2],
+            valid: 1,
+        },
+        errors: 3,
+    };
+    KeyStorePtr::from_mut(&mut regs).secure_clear();
+    assert_eq!(regs.status, 1);
+    assert_eq!(regs.key, [0; 4]);
+    assert_eq!(regs.iv.words, [[0; 2]; 2]);
+    assert_eq!(regs.iv.valid, 1);
+    assert_eq!(regs.errors, 3);
+
+    // a write-only view of the map can be cleared too
+    let mut regs = KeyStore {
+        key: [7; 4],
+        ..Default::default()
+    };
+    let ptr = KeyStorePtr::from_mut(&mut regs);
+    let iv: KeyStoreIvPtr<reg_map::access::WriteOnly> = ptr.iv();
+    iv.secure_clear();
+    ptr.secure_clear();
+    assert_eq!(regs.key, [0; 4]);
+
+    // byte-array registers are cleared with a single write
+    let mut regs = Crypto {
+        key: [0xff; 16],
+        digest: [1, 2, 3, 4],
+        plain: [5; 4],
+    };
+    CryptoPtr::from_mut(&mut regs).secure_clear();
+    assert_eq!(regs.key, [0; 16]);
+    assert_eq!(regs.digest, [1, 2, 3, 4]);
+    assert_eq!(regs.plain, [0; 4]);
+    // non-zero registers are skipped, since zero is not a valid value for them
+    let one = core::num::NonZeroU16::MIN;
+    let mut regs = ClearNonZero {
+        divider: core::num::NonZeroU32::MAX,
+        data: 5,
+        table: [one; 2],
+    };
+    ClearNonZeroPtr::from_mut(&mut regs).secure_clear();
+    assert_eq!(regs.divider, core::num::NonZeroU32::MAX);
+    assert_eq!(regs.data, 0);
+    assert_eq!(regs.table, [one; 2]);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct Lanes {
+    data: [u64; 10],
+}
+
+#[test]
+fn array_chunks() {
+    let mut regs = Lanes::default();
+    let ptr = LanesPtr::from_mut(&mut regs);
+
+    let lens: Vec<usize> = ptr.data().chunks(4).map(|chunk| chunk.len()).collect();
+    assert_eq!(lens, [4, 4, 2]);
+    assert_eq!(ptr.data().chunks(5).len(), 2);
+    assert_eq!(ptr.data().chunks(20).len(), 1);
+
+    for (i, chunk) in ptr.data().chunks(4).enumerate() {
+        for (j, reg) in chunk.iter().enumerate() {
+            reg.write((i * 10 + j) as u64);
+        }
+    }
+    assert_eq!(regs.data, [0, 1, 2, 3, 10, 11, 12, 13, 20, 21]);
+
+    let ptr = LanesPtr::from_mut(&mut regs);
+    let last = ptr.data().chunks(4).next_back().unwrap();
+    assert_eq!(last.idx(1).read(), 21);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn array_chunks_zero() {
+    let mut regs = Lanes::default();
+    let ptr = LanesPtr::from_mut(&mut regs);
+    let _ = ptr.data().chunks(0);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct Versioned {
+    #[reg(RO)]
+    version: u32,
+    #[reg(RO)]
+    data: u64,
+}
+
+#[test]
+fn read_versioned() {
+    let mut regs = Versioned {
+        version: 3,
+        data: 0x1234,
+    };
+    let ptr = VersionedPtr::from_mut(&mut regs);
+    assert_eq!(ptr.data().read_versioned(&ptr.version()), (3, 0x1234));
+
+    // the hardware updates the data and bumps the version
+    let raw = ptr.as_ptr();
+    unsafe {
+        (*raw).data = 0x5678;
+        (*raw).version = 4;
+    }
+    let (version, data) = ptr.data().read_versioned(&ptr.version());
+    assert_eq!((version, data), (4, 0x5678));
+    assert_eq!(ptr.version().read(), version);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+struct Telemetry {
+    samples: [u64; 8],
+}
+
+#[test]
+fn array_windows() {
+    use reg_map::access::ReadWrite;
+    use reg_map::{Reg, RegSlice};
+
+    let mut regs = Telemetry {
+        samples: [10, 20, 30, 40, 50, 60, 70, 80],
+    };
+    let ptr = TelemetryPtr::from_mut(&mut regs);
+
+    let mut windows = ptr.samples().windows(3);
+    assert_eq!(windows.len(), 6);
+    fn read<'a>(window: RegSlice<'a, Reg<'a, u64, ReadWrite>>) -> Vec<u64> {
+        window.iter().map(|reg| reg.read()).collect()
+    }
+    assert_eq!(read(windows.next().unwrap()), [10, 20, 30]);
+    assert_eq!(read(windows.next_back().unwrap()), [60, 70, 80]);
+    assert_eq!(windows.len(), 4);
+
+    // moving average
+    let averages: Vec<u64> = ptr
+        .samples()
+        .windows(4)
+        .map(|window| window.iter().map(|reg| reg.read()).sum::<u64>() / 4)
+        .collect();
+    assert_eq!(averages, [25, 35, 45, 55, 65]);
+
+    assert_eq!(ptr.samples().windows(8).len(), 1);
+    assert_eq!(ptr.samples().windows(9).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "window size must be non-zero")]
+fn array_windows_zero() {
+    let mut regs = Telemetry::default();
+    let ptr = TelemetryPtr::from_mut(&mut regs);
+    let _ = ptr.samples().windows(0);
+}
+
+#[test]
+fn array_split_at() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+
+    let (lower, upper) = ptr.field2().split_at(16);
+    assert_eq!((lower.len(), upper.len()), (16, 16));
+    for (i, reg) in lower.iter().enumerate() {
+        reg.write(i as u64);
+    }
+    for (i, reg) in upper.iter().enumerate() {
+        reg.write(100 + i as u64);
+    }
+    assert_eq!(lower.idx(15).read(), 15);
+    assert_eq!(upper.idx(0).read(), 100);
+    let expected: [u64; 32] = core::array::from_fn(|i| {
+        if i < 16 {
+            i as u64
+        } else {
+            100 + i as u64 - 16
+        }
+    });
+    assert_eq!(regs.field2, expected);
+
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let (all, empty) = ptr.field2().split_at(32);
+    assert_eq!((all.len(), empty.len()), (32, 0));
+}
+
+#[test]
+#[should_panic]
+fn array_split_at_out_of_bounds() {
+    let mut regs = Array::default();
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let _ = ptr.field2().split_at(core::hint::black_box(33));
+}
+
+#[test]
+fn array_types() {
+    use reg_map::{ArrayElem, ArrayTypes, RegArray};
+
+    type Field2<'a> = RegArray<'a, reg_map::Reg<'a, u64, reg_map::access::ReadWrite>, 32>;
+
+    fn last<'a, P: ArrayElem<'a>, const N: usize>(
+        arr: &RegArray<'a, P, N>,
+    ) -> Option<<RegArray<'a, P, N> as ArrayTypes<'a>>::Elem> {
+        arr.get_last()
+    }
+    fn target_size<'a, R: ArrayTypes<'a>>() -> usize {
+        core::mem::size_of::<R::Target>()
+    }
+
+    let mut regs = Array::default();
+    regs.field2[31] = 9;
+    let ptr = ArrayPtr::from_mut(&mut regs);
+    let elem: <Field2 as ArrayTypes>::Elem = last(&ptr.field2()).unwrap();
+    assert_eq!(elem.read(), 9);
+    assert_eq!(target_size::<Field2>(), 8);
+    assert_eq!(
+        target_size::<RegArray<SubArrayPtr, 2>>(),
+        size_of::<SubArray>()
+    );
+}
+
+#[test]
+fn array_idx2_idx3() {
+    let mut regs = Array4d::default();
+    let ptr = Array4dPtr::from_mut(&mut regs);
+
+    for i in 0..7 {
+        for j in 0..5 {
+            for k in 0..3 {
+                for l in 0..2 {
+                    let chained = ptr.data().idx(i).idx(j).idx(k).idx(l).as_ptr();
+                    assert_eq!(ptr.data().idx(i).idx2(j, k).idx(l).as_ptr(), chained);
+                    assert_eq!(ptr.data().idx2(i, j).idx2(k, l).as_ptr(), chained);
+                    assert_eq!(ptr.data().idx3(i, j, k).idx(l).as_ptr(), chained);
+                    assert_eq!(ptr.data().idx(i).idx3(j, k, l).as_ptr(), chained);
+                }
+            }
+        }
+    }
+
+    ptr.data().idx3(6, 4, 2).idx(1).write(42);
+    assert_eq!(regs.data[6][4][2][1], 42);
+
+    let mut regs = Array4dComplex::default();
+    let ptr = Array4dComplexPtr::from_mut(&mut regs);
+    ptr.data().idx2(3, 1).idx2(2, 0).field2().write(7);
+    assert_eq!(regs.data[3][1][2][0].field2, 7);
+}
+
+#[test]
+#[should_panic]
+fn array_idx2_out_of_bounds() {
+    let mut regs = Array4d::default();
+    let ptr = Array4dPtr::from_mut(&mut regs);
+    let _ = ptr.data().idx2(0, core::hint::black_box(5));
+}
+
+#[test]
+#[should_panic]
+fn array_idx3_out_of_bounds() {
+    let mut regs = Array4d::default();
+    let ptr = Array4dPtr::from_mut(&mut regs);
+    let _ = ptr.data().idx3(0, 0, core::hint::black_box(3));
+}
+
+#[cfg(feature = "fixed")]
+#[test]
+fn read_write_fixed() {
+    use fixed::types::extra::{U16, U8};
+    use fixed::{FixedU16, FixedU32};
+
+    let mut regs = Fields {
+        id: 0x0180,
+        ..Default::default()
+    };
+    let ptr = FieldsPtr::from_mut(&mut regs);
+
+    let val = FixedU32::<U16>::from_num(3.25);
+    ptr.data().write_fixed(val);
+    assert_eq!(regs.data, 0x0003_4000);
+
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    assert_eq!(ptr.data().read_fixed::<FixedU32<U16>>(), val);
+    assert_eq!(ptr.id().read_fixed::<FixedU16<U8>>(), 1.5);
+}
+
+#[test]
+fn array_extend_into() {
+    use std::collections::VecDeque;
+
+    let mut regs = Telemetry {
+        samples: [1, 2, 3, 4, 5, 6, 7, 8],
+    };
+    let ptr = TelemetryPtr::from_mut(&mut regs);
+
+    let mut vec = vec![0];
+    ptr.samples().extend_into(&mut vec);
+    assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let mut deque = VecDeque::from([0]);
+    ptr.samples().extend_into(&mut deque);
+    deque.pop_front();
+    ptr.samples().extend_into(&mut deque);
+    assert_eq!(deque.len(), 16);
+    assert!(deque.iter().eq(regs.samples.iter().chain(&regs.samples)));
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(zeroed)]
+struct Flags {
+    enable: bool,
+    #[reg(RO)]
+    ready: bool,
+    lanes: [bool; 4],
+    count: u8,
+}
+
+#[test]
+fn bool_registers() {
+    let mut regs = Flags {
+        ready: true,
+        ..Default::default()
+    };
+    let ptr = FlagsPtr::from_mut(&mut regs);
+
+    assert!(!ptr.enable().read());
+    ptr.enable().write(true);
+    assert!(ptr.enable().read());
+    assert!(ptr.ready().read());
+    ptr.lanes().idx(2).write(true);
+    assert_eq!(ptr.lanes().read_array(), [false, false, true, false]);
+    assert_eq!(regs.lanes, [false, false, true, false]);
+    assert!(regs.enable);
+
+    // any non-zero byte reads as `true`
+    let mut regs = Flags::default();
+    let ptr = FlagsPtr::from_mut(&mut regs);
+    unsafe { ptr.as_ptr().cast::<u8>().write_volatile(2) };
+    assert!(ptr.enable().read());
+    ptr.enable().write(false);
+    assert!(!ptr.enable().read());
+
+    let zeroed = Flags::ZEROED;
+    assert!(!zeroed.enable && !zeroed.ready && zeroed.lanes == [false; 4]);
+}
+
+#[test]
+fn read_write_with_flavor() {
+    use core::ptr::NonNull;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use reg_map::{DefaultVolatile, VolatileOp};
+
+    static ACCESSES: AtomicUsize = AtomicUsize::new(0);
+
+    /// Counts the accesses, and swaps the bytes written to the register.
+    struct Swapped;
+    impl VolatileOp<u32> for Swapped {
+        unsafe fn read(ptr: NonNull<u32>) -> u32 {
+            ACCESSES.fetch_add(1, Ordering::Relaxed);
+            unsafe { ptr.read_volatile() }.swap_bytes()
+        }
+        unsafe fn write(ptr: NonNull<u32>, val: u32) {
+            ACCESSES.fetch_add(1, Ordering::Relaxed);
+            unsafe { ptr.write_volatile(val.swap_bytes()) }
+        }
+    }
+
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    let data = ptr.data();
+
+    // the default flavor matches `read` and `write`
+    data.write_with(DefaultVolatile, 0x1234_5678);
+    assert_eq!(data.read(), 0x1234_5678);
+    data.write(0x0bad_cafe);
+    assert_eq!(data.read_with(DefaultVolatile), 0x0bad_cafe);
+
+    data.write_with(Swapped, 0x1122_3344);
+    assert_eq!(data.read(), 0x4433_2211);
+    assert_eq!(data.read_with(Swapped), 0x1122_3344);
+    assert_eq!(ACCESSES.load(Ordering::Relaxed), 2);
+    assert_eq!(regs.data, 0x4433_2211);
+}
+
+#[repr(C)]
+#[derive(RegMap, Default)]
+#[reg_map(zeroed)]
+struct Coefficients {
+    gain: f32,
+    #[reg(RO)]
+    scale: f64,
+    taps: [f32; 4],
+    wide: [[f64; 2]; 2],
+}
+
+#[test]
+fn float_registers() {
+    let mut regs = Coefficients {
+        scale: -2.5,
+        ..Default::default()
+    };
+    let ptr = CoefficientsPtr::from_mut(&mut regs);
+
+    for val in [0.0, -0.0, -3.75, f32::INFINITY, f32::MIN_POSITIVE] {
+        ptr.gain().write(val);
+        assert_eq!(ptr.gain().read().to_bits(), val.to_bits());
+    }
+    ptr.gain().write(f32::NAN);
+    assert!(ptr.gain().read().is_nan());
+    assert_eq!(ptr.scale().read(), -2.5);
+
+    ptr.taps().write_array([1.0, -1.0, f32::NEG_INFINITY, 0.25]);
+    assert_eq!(
+        ptr.taps().read_array(),
+        [1.0, -1.0, f32::NEG_INFINITY, 0.25]
+    );
+    for val in [0.0, -1e300, f64::INFINITY] {
+        ptr.wide().idx2(1, 0).write(val);
+        assert_eq!(ptr.wide().idx(1).idx(0).read(), val);
+    }
+    assert_eq!(regs.taps, [1.0, -1.0, f32::NEG_INFINITY, 0.25]);
+    assert_eq!(regs.wide[1][0], f64::INFINITY);
+
+    let zeroed = Coefficients::ZEROED;
+    assert_eq!(zeroed.gain, 0.0);
+    assert_eq!(zeroed.wide, [[0.0; 2]; 2]);
+}
+
+#[test]
+fn nested_offset_constants() {
+    let mut regs = CAOuter::default();
+    let ptr = CAOuterPtr::from_mut(&mut regs);
+    let base = ptr.as_ptr();
+
+    // rebuild the pointer to a nested map from the base address and the offset constants
+    let elem = CAOuterPtr::OUTER_OFFSET + 3 * size_of::<CAInner>();
+    let inner = unsafe { CAInnerPtr::from_ptr(base.byte_add(elem).cast()) };
+    assert_eq!(inner.as_ptr(), ptr.outer().idx(3).as_ptr());
+    inner.inner().idx(1).write(5);
+    assert_eq!(ptr.outer().idx(3).inner().idx(1).read(), 5);
+
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    let inner =
+        unsafe { SimplePtr::from_ptr(ptr.as_ptr().byte_add(FieldsPtr::INNER_OFFSET).cast()) };
+    assert_eq!(inner.as_ptr(), ptr.inner().as_ptr());
+    assert_eq!(
+        inner.field2().as_ptr(),
+        ptr.field_addr(FieldsPtr::INNER_OFFSET + SimplePtr::FIELD2_OFFSET)
+            .cast()
+    );
+}
+
+#[test]
+fn read_write_enum() {
+    use reg_map::integers::RegEnum;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum LinkState {
+        Down = 0,
+        Polling = 1,
+        Training = 2,
+        Up = 5,
+    }
+    impl RegEnum for LinkState {
+        type Repr = u32;
+        fn from_repr(repr: u32) -> Option<Self> {
+            match repr {
+                0 => Some(Self::Down),
+                1 => Some(Self::Polling),
+                2 => Some(Self::Training),
+                5 => Some(Self::Up),
+                _ => None,
+            }
+        }
+        fn to_repr(&self) -> u32 {
+            *self as u32
+        }
+    }
+
+    let mut regs = Fields::default();
+    let ptr = FieldsPtr::from_mut(&mut regs);
+    let data = ptr.data();
+
+    assert_eq!(data.read_enum(), Some(LinkState::Down));
+    data.write_enum(LinkState::Up);
+    assert_eq!(data.read(), 5);
+    assert_eq!(data.read_enum(), Some(LinkState::Up));
+    data.write_enum(LinkState::Training);
+    assert_eq!(data.read_enum::<LinkState>(), Some(LinkState::Training));
+
+    // unrecognized values read as `None`
+    data.write(3);
+    assert_eq!(data.read_enum::<LinkState>(), None);
+    data.write(0xffff_ffff);
+    assert_eq!(data.read_enum::<LinkState>(), None);
+}
+
+#[test]
+fn from_static() {
+    static mut BACKING: Simple = Simple {
+        field1: 1,
+        field2: 2,
+    };
+
+    let ptr: SimplePtr<'static> =
+        unsafe { SimplePtr::from_static(core::ptr::addr_of_mut!(BACKING)) };
+    assert_eq!(ptr.field1().read(), 1);
+    ptr.field2().write(20);
+    assert_eq!(ptr.field2().read(), 20);
+
+    let leaked: &'static mut Array = Box::leak(Box::default());
+    let raw: *mut Array = leaked;
+    let ptr = unsafe { ArrayPtr::from_static(raw) };
+    ptr.field2().idx(31).write(7);
+    // the pointer can be stored wherever a `'static` lifetime is required
+    struct Driver {
+        regs: ArrayPtr<'static>,
+    }
+    let driver = Driver { regs: ptr };
+    assert_eq!(driver.regs.field2().idx(31).read(), 7);
+    assert_eq!(unsafe { (*raw).field2[31] }, 7);
+}